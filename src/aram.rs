@@ -0,0 +1,84 @@
+/// Picks the bench champion worth swapping to, if any.
+/// A swap is only worth it when the bench champion is higher on the wishlist than the current one.
+///
+/// ### Arguments:
+/// * `wishlist`: Champion ids in priority order, highest first.
+/// * `current_champion`: The champion id the local player currently has.
+/// * `bench`: The champion ids currently on the bench.
+pub fn best_bench_swap(wishlist: &[u32], current_champion: u32, bench: &[u32]) -> Option<u32> {
+    let rank = |champion_id: u32| wishlist.iter().position(|id| *id == champion_id);
+    let current_rank = rank(current_champion).unwrap_or(usize::MAX);
+
+    bench
+        .iter()
+        .filter_map(|champion_id| rank(*champion_id).map(|rank| (rank, *champion_id)))
+        .filter(|(rank, _)| *rank < current_rank)
+        .min()
+        .map(|(_, champion_id)| champion_id)
+}
+
+/// Reads the champion ids on the bench from a champion select session.
+pub fn bench_champions(champ_select: &serde_json::Value) -> Vec<u32> {
+    champ_select["benchChampions"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(|champion| champion["championId"].as_u64())
+        .map(|champion_id| champion_id as u32)
+        .collect()
+}
+
+/// Swaps the current champion with one on the bench.
+///
+/// ### Arguments:
+/// * `lcu`: The client the LeagueClient requests go through.
+/// * `champion_id`: The id of the bench champion to swap to.
+pub async fn swap_with_bench(
+    lcu: &crate::lcu::LcuClient,
+    champion_id: u32,
+) -> Result<(), crate::lcu::Error> {
+    lcu.post(
+        &format!("/lol-champ-select/v1/session/bench/swap/{}", champion_id),
+        None,
+    )
+    .await?;
+
+    Ok(())
+}
+
+/// Rerolls the current champion, the old one goes to the bench.
+///
+/// ### Arguments:
+/// * `lcu`: The client the LeagueClient requests go through.
+pub async fn reroll(lcu: &crate::lcu::LcuClient) -> Result<(), crate::lcu::Error> {
+    lcu.post("/lol-champ-select/v1/session/my-selection/reroll", None)
+        .await?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const WISHLIST: [u32; 4] = [22, 51, 145, 64];
+
+    #[test]
+    fn any_wishlist_champion_beats_one_off_the_wishlist() {
+        assert_eq!(best_bench_swap(&WISHLIST, 1, &[64]), Some(64));
+        assert_eq!(best_bench_swap(&WISHLIST, 1, &[2, 3]), None);
+        assert_eq!(best_bench_swap(&[], 1, &[22]), None);
+    }
+
+    #[test]
+    fn lower_ranked_bench_champions_are_ignored() {
+        assert_eq!(best_bench_swap(&WISHLIST, 51, &[145, 64, 7]), None);
+        assert_eq!(best_bench_swap(&WISHLIST, 22, &[51]), None);
+    }
+
+    #[test]
+    fn highest_ranked_bench_champion_wins() {
+        assert_eq!(best_bench_swap(&WISHLIST, 64, &[145, 51, 7]), Some(51));
+        assert_eq!(best_bench_swap(&WISHLIST, 1, &[64, 22, 145]), Some(22));
+    }
+}
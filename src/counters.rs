@@ -0,0 +1,150 @@
+use std::collections::HashMap;
+
+/// Counter-pick scores read from `counters.json`, keyed by the enemy champion id and then by the id of the
+/// champion playing against it. Positive scores are good matchups for us, negative scores are bad ones.
+pub type CounterTable = HashMap<u32, HashMap<u32, i32>>;
+
+/// How much more the direct lane opponent weighs compared to the rest of the enemy team.
+const LANE_OPPONENT_WEIGHT: i32 = 3;
+
+/// Reads the counter-pick table, an empty table if the file is missing so the pick order is left untouched.
+/// A malformed file is an error, so it can be told apart from a file without matchups.
+pub fn load_counters(path: &str) -> Result<CounterTable, String> {
+    match std::fs::read_to_string(path) {
+        Ok(data) => serde_json::from_str(&data)
+            .map_err(|error| format!("Couldn't parse {}: {}", path, error)),
+        Err(_) => Ok(CounterTable::new()),
+    }
+}
+
+/// Reads the enemy champion ids from a champion select session, along with the lane opponent
+/// of the local player if the client exposes enemy positions.
+///
+/// ### Arguments:
+/// * `champ_select`: The `/lol-champ-select/v1/session` response.
+/// * `assigned_position`: The position of the local player, empty in queues without positions.
+pub fn enemy_champions(
+    champ_select: &serde_json::Value,
+    assigned_position: &str,
+) -> (Vec<u32>, Option<u32>) {
+    let mut enemies = Vec::new();
+    let mut lane_opponent = None;
+
+    for enemy in champ_select["theirTeam"].as_array().into_iter().flatten() {
+        let champion_id = enemy["championId"].as_u64().unwrap_or(0) as u32;
+        if champion_id == 0 {
+            continue;
+        }
+        if !assigned_position.is_empty() && enemy["assignedPosition"] == assigned_position {
+            lane_opponent = Some(champion_id);
+        }
+        enemies.push(champion_id);
+    }
+
+    (enemies, lane_opponent)
+}
+
+/// Sorts the pick pool from best to worst counter against the enemy team.
+/// Champions with equal scores keep their original priority order.
+///
+/// ### Arguments:
+/// * `pool`: The champion ids we're willing to pick, in priority order.
+/// * `counters`: The counter-pick table.
+/// * `enemies`: The champion ids picked by the enemy team.
+/// * `lane_opponent`: The champion id of our lane opponent, if known.
+pub fn order_by_counters(
+    pool: &[u32],
+    counters: &CounterTable,
+    enemies: &[u32],
+    lane_opponent: Option<u32>,
+) -> Vec<u32> {
+    let score = |champion_id: u32| -> i32 {
+        enemies
+            .iter()
+            .map(|enemy_id| {
+                let matchup = counters
+                    .get(enemy_id)
+                    .and_then(|scores| scores.get(&champion_id))
+                    .copied()
+                    .unwrap_or(0);
+
+                if Some(*enemy_id) == lane_opponent {
+                    matchup * LANE_OPPONENT_WEIGHT
+                } else {
+                    matchup
+                }
+            })
+            .sum()
+    };
+
+    let mut ordered = pool.to_vec();
+    ordered.sort_by_key(|champion_id| std::cmp::Reverse(score(*champion_id)));
+    ordered
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn table() -> CounterTable {
+        serde_json::from_str(
+            r#"{
+                "1": { "10": 2, "20": -1 },
+                "2": { "20": 3, "30": 1 }
+            }"#,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn pool_is_sorted_by_total_score() {
+        assert_eq!(
+            order_by_counters(&[10, 20, 30], &table(), &[1, 2], None),
+            vec![10, 20, 30]
+        );
+        assert_eq!(
+            order_by_counters(&[30, 10], &table(), &[2], None),
+            vec![30, 10]
+        );
+    }
+
+    #[test]
+    fn lane_opponent_weighs_more() {
+        // 20 scores -1 * 3 + 3 against its lane opponent, 10 scores 2 * 3
+        assert_eq!(
+            order_by_counters(&[20, 10], &table(), &[1, 2], Some(1)),
+            vec![10, 20]
+        );
+        // 20 scores -1 + 3 * 3 when the lane opponent is the other champion
+        assert_eq!(
+            order_by_counters(&[10, 20], &table(), &[1, 2], Some(2)),
+            vec![20, 10]
+        );
+    }
+
+    #[test]
+    fn equal_scores_keep_the_priority_order() {
+        assert_eq!(
+            order_by_counters(&[40, 30, 50], &table(), &[1], None),
+            vec![40, 30, 50]
+        );
+        assert_eq!(
+            order_by_counters(&[20, 10], &CounterTable::new(), &[1, 2], Some(1)),
+            vec![20, 10]
+        );
+    }
+
+    #[test]
+    fn malformed_file_is_an_error() {
+        let path = std::env::temp_dir().join("counters_malformed_test.json");
+        std::fs::write(&path, "{ \"1\": [ }").unwrap();
+        let result = load_counters(path.to_str().unwrap());
+        std::fs::remove_file(&path).unwrap();
+
+        assert!(result.is_err());
+        assert_eq!(
+            load_counters("./does/not/exist.json"),
+            Ok(CounterTable::new())
+        );
+    }
+}
@@ -0,0 +1,224 @@
+use serde::Serialize;
+
+#[derive(Debug, Serialize, Clone, Default, PartialEq)]
+/// The `Cell` struct is one player slot of a champion select.
+///
+/// ### Properties:
+/// * `cell_id`: The id of the cell, actions refer to it as `actorCellId`.
+/// * `champion_id`: The champion picked or hovered by the player, `0` if there is none.
+/// * `pick_intent`: The champion the player declared during the planning phase, `0` if there is none.
+/// * `locked`: Whether the pick of the player is locked in.
+/// * `position`: The assigned position, empty in queues without positions.
+/// * `spell1_id`: The key of the first summoner spell, unknown (`0`) for the enemy team.
+/// * `spell2_id`: The key of the second summoner spell, unknown (`0`) for the enemy team.
+pub struct Cell {
+    pub cell_id: u64,
+    pub champion_id: u32,
+    pub pick_intent: u32,
+    pub locked: bool,
+    pub position: String,
+    pub spell1_id: u32,
+    pub spell2_id: u32,
+}
+
+#[derive(Debug, Serialize, Clone, PartialEq)]
+/// The `Action` struct is a pick or ban action that is currently in progress.
+///
+/// ### Properties:
+/// * `kind`: The type of the action, usually `pick` or `ban`.
+/// * `actor_cell_id`: The cell of the player the action belongs to.
+/// * `champion_id`: The champion currently hovered for the action, `0` if there is none.
+/// * `ally`: Whether the player belongs to our team.
+pub struct Action {
+    pub kind: String,
+    pub actor_cell_id: u64,
+    pub champion_id: u32,
+    pub ally: bool,
+}
+
+#[derive(Debug, Serialize, Clone, Default, PartialEq)]
+/// The `Draft` struct is the state of a champion select, as read from `/lol-champ-select/v1/session`.
+///
+/// ### Properties:
+/// * `local_cell_id`: The cell of the player running the app.
+/// * `my_team`: The cells of our team.
+/// * `their_team`: The cells of the enemy team.
+/// * `my_bans`: The champions banned by our team.
+/// * `their_bans`: The champions banned by the enemy team.
+/// * `current_actions`: The actions in progress, bans can happen simultaneously.
+/// * `timer_phase`: The phase of the timer (`PLANNING`, `BAN_PICK`, `FINALIZATION`...).
+/// * `time_left_ms`: The time left in the timer phase when the session was read, in milliseconds.
+pub struct Draft {
+    pub local_cell_id: u64,
+    pub my_team: Vec<Cell>,
+    pub their_team: Vec<Cell>,
+    pub my_bans: Vec<u32>,
+    pub their_bans: Vec<u32>,
+    pub current_actions: Vec<Action>,
+    pub timer_phase: String,
+    pub time_left_ms: u64,
+}
+
+impl Draft {
+    /// Reads the draft out of a champion select session, with the time left at `now_ms`.
+    pub fn from_session(session: &serde_json::Value, now_ms: u64) -> Self {
+        let actions: Vec<&serde_json::Value> = session["actions"]
+            .as_array()
+            .into_iter()
+            .flatten()
+            .filter_map(|group| group.as_array())
+            .flatten()
+            .collect();
+        let locked_cells: Vec<u64> = actions
+            .iter()
+            .filter(|action| action["type"] == "pick" && action["completed"] == true)
+            .filter_map(|action| action["actorCellId"].as_u64())
+            .collect();
+
+        let team = |key: &str| -> Vec<Cell> {
+            session[key]
+                .as_array()
+                .into_iter()
+                .flatten()
+                .map(|member| {
+                    let cell_id = member["cellId"].as_u64().unwrap_or(0);
+                    Cell {
+                        cell_id,
+                        champion_id: member["championId"].as_u64().unwrap_or(0) as u32,
+                        pick_intent: member["championPickIntent"].as_u64().unwrap_or(0) as u32,
+                        locked: locked_cells.contains(&cell_id),
+                        position: member["assignedPosition"]
+                            .as_str()
+                            .unwrap_or_default()
+                            .to_owned(),
+                        spell1_id: member["spell1Id"].as_u64().unwrap_or(0) as u32,
+                        spell2_id: member["spell2Id"].as_u64().unwrap_or(0) as u32,
+                    }
+                })
+                .collect()
+        };
+        let my_team = team("myTeam");
+        let their_team = team("theirTeam");
+        let is_ally = |cell_id: u64| my_team.iter().any(|cell| cell.cell_id == cell_id);
+
+        let mut my_bans = Vec::new();
+        let mut their_bans = Vec::new();
+        for action in actions
+            .iter()
+            .filter(|action| action["type"] == "ban" && action["completed"] == true)
+        {
+            let champion_id = action["championId"].as_u64().unwrap_or(0) as u32;
+            if champion_id == 0 {
+                continue;
+            }
+            if is_ally(action["actorCellId"].as_u64().unwrap_or(0)) {
+                my_bans.push(champion_id);
+            } else {
+                their_bans.push(champion_id);
+            }
+        }
+
+        let current_actions = actions
+            .iter()
+            .filter(|action| action["isInProgress"] == true)
+            .map(|action| {
+                let actor_cell_id = action["actorCellId"].as_u64().unwrap_or(0);
+                Action {
+                    kind: action["type"].as_str().unwrap_or_default().to_owned(),
+                    actor_cell_id,
+                    champion_id: action["championId"].as_u64().unwrap_or(0) as u32,
+                    ally: is_ally(actor_cell_id),
+                }
+            })
+            .collect();
+
+        Self {
+            local_cell_id: session["localPlayerCellId"].as_u64().unwrap_or(0),
+            my_bans,
+            their_bans,
+            current_actions,
+            timer_phase: session["timer"]["phase"]
+                .as_str()
+                .unwrap_or_default()
+                .to_owned(),
+            time_left_ms: time_left_ms(&session["timer"], now_ms).unwrap_or(0),
+            my_team,
+            their_team,
+        }
+    }
+
+    /// Whether one of the actions in progress belongs to the local player.
+    pub fn is_local_turn(&self) -> bool {
+        self.current_actions
+            .iter()
+            .any(|action| action.ally && action.actor_cell_id == self.local_cell_id)
+    }
+}
+
+/// The current time as a Unix timestamp in milliseconds, the clock the LeagueClient timers use.
+pub fn now_ms() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|duration| duration.as_millis() as u64)
+        .unwrap_or(0)
+}
+
+/// The time left in the timer phase at `now_ms`, in milliseconds, `None` if the session has no timer.
+/// `adjustedTimeLeftInPhase` is the time left when the LeagueClient last updated the timer (`internalNowInEpochMs`),
+/// so the time since that update is taken off.
+pub fn time_left_ms(timer: &serde_json::Value, now_ms: u64) -> Option<u64> {
+    let time_left = timer["adjustedTimeLeftInPhase"].as_f64()?;
+    let since_update = timer["internalNowInEpochMs"]
+        .as_f64()
+        .map(|updated| (now_ms as f64 - updated).max(0.0))
+        .unwrap_or(0.0);
+    Some((time_left - since_update).max(0.0) as u64)
+}
+
+/// Whether a pick locked `seconds` before the end of the timer should be locked at `now_ms`.
+pub fn late_lock_due(timer: &serde_json::Value, now_ms: u64, seconds: u64) -> bool {
+    matches!(time_left_ms(timer, now_ms), Some(time_left) if time_left <= seconds * 1000)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn timer(time_left: f64, updated: u64) -> serde_json::Value {
+        serde_json::json!({
+            "phase": "BAN_PICK",
+            "adjustedTimeLeftInPhase": time_left,
+            "internalNowInEpochMs": updated,
+        })
+    }
+
+    #[test]
+    fn time_left_counts_down_from_the_last_update() {
+        let timer = timer(29_874.5, 1_000_000);
+
+        assert_eq!(time_left_ms(&timer, 1_000_000), Some(29_874));
+        assert_eq!(time_left_ms(&timer, 1_010_000), Some(19_874));
+        assert_eq!(time_left_ms(&timer, 1_040_000), Some(0));
+        // A clock slightly behind the LeagueClient doesn't add time
+        assert_eq!(time_left_ms(&timer, 999_000), Some(29_874));
+    }
+
+    #[test]
+    fn time_left_without_update_time_is_taken_as_is() {
+        let timer = serde_json::json!({ "adjustedTimeLeftInPhase": 12_500.0 });
+
+        assert_eq!(time_left_ms(&timer, 1_000_000), Some(12_500));
+        assert_eq!(time_left_ms(&serde_json::Value::Null, 1_000_000), None);
+    }
+
+    #[test]
+    fn late_lock_is_due_once_the_threshold_is_reached() {
+        let timer = timer(30_000.0, 1_000_000);
+
+        assert!(!late_lock_due(&timer, 1_000_000, 5));
+        assert!(!late_lock_due(&timer, 1_024_999, 5));
+        assert!(late_lock_due(&timer, 1_025_000, 5));
+        assert!(late_lock_due(&timer, 1_031_000, 5));
+        assert!(!late_lock_due(&serde_json::Value::Null, 1_000_000, 5));
+    }
+}
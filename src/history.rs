@@ -0,0 +1,188 @@
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::io::Write;
+
+/// File the observed games are appended to, one JSON object per line.
+pub const HISTORY_PATH: &str = "./utils/match_history.jsonl";
+/// How long after a game the end of game stats are waited for, the game is recorded without a result after that.
+pub const RESULT_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(60);
+
+#[derive(Debug, Deserialize, Serialize, Clone, Default, PartialEq)]
+/// The `GameRecord` struct is one game observed by the app.
+///
+/// ### Properties:
+/// * `timestamp`: When the game was recorded, in seconds since the Unix epoch.
+/// * `game_id`: The id of the game.
+/// * `queue_id`: The id of the queue the game was played in.
+/// * `champion_id`: The champion played.
+/// * `position`: The assigned position, empty in queues without positions.
+/// * `spell1_id`: The key of the first summoner spell.
+/// * `spell2_id`: The key of the second summoner spell.
+/// * `auto_picked`: Whether the champion was locked in by auto-pick.
+/// * `win`: The result of the game, `None` if the end of game stats couldn't be read.
+pub struct GameRecord {
+    pub timestamp: u64,
+    pub game_id: u64,
+    pub queue_id: u32,
+    pub champion_id: u32,
+    pub position: String,
+    pub spell1_id: u32,
+    pub spell2_id: u32,
+    pub auto_picked: bool,
+    pub win: Option<bool>,
+}
+
+/// Reads every game from the history file, lines that can't be parsed are skipped.
+pub fn load_history(path: &str) -> Vec<GameRecord> {
+    std::fs::read_to_string(path)
+        .unwrap_or_default()
+        .lines()
+        .filter_map(|line| serde_json::from_str(line).ok())
+        .collect()
+}
+
+/// Appends a game to the history file, creating it if needed.
+pub fn append_record(path: &str, record: &GameRecord) -> std::io::Result<()> {
+    let mut file = std::fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)?;
+    writeln!(file, "{}", serde_json::to_string(record)?)
+}
+
+/// Reads the result of a game from the end of game stats, `None` if the stats belong to another game.
+///
+/// ### Arguments:
+/// * `lcu`: The client the LeagueClient requests go through.
+/// * `game_id`: The id of the game the result is wanted for.
+pub async fn fetch_result(
+    lcu: &crate::lcu::LcuClient,
+    game_id: u64,
+) -> Result<Option<bool>, crate::lcu::Error> {
+    let stats = lcu.get("/lol-end-of-game/v1/eog-stats-block").await?;
+
+    if stats["gameId"].as_u64() != Some(game_id) {
+        return Ok(None);
+    }
+
+    let player_team_won = stats["teams"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .find(|team| team["isPlayerTeam"] == true)
+        .and_then(|team| team["isWinningTeam"].as_bool());
+
+    Ok(player_team_won.or_else(|| {
+        stats["localPlayer"]["stats"]["WIN"]
+            .as_u64()
+            .map(|win| win == 1)
+    }))
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+/// The `Filter` struct narrows the games shown in the history tab, `None` matches everything.
+pub struct Filter {
+    pub champion_id: Option<u32>,
+    pub position: Option<String>,
+    pub queue_id: Option<u32>,
+}
+
+impl Filter {
+    /// Whether the game passes every filter that is set.
+    pub fn matches(&self, record: &GameRecord) -> bool {
+        !matches!(self.champion_id, Some(id) if id != record.champion_id)
+            && !matches!(&self.position, Some(position) if *position != record.position)
+            && !matches!(self.queue_id, Some(id) if id != record.queue_id)
+    }
+}
+
+/// Groups games by the given key and counts `(key, games with a result, wins)`, sorted by key.
+pub fn win_rates<'a, K: Ord>(
+    records: impl IntoIterator<Item = &'a GameRecord>,
+    key: impl Fn(&GameRecord) -> K,
+) -> Vec<(K, u32, u32)> {
+    let mut groups: BTreeMap<K, (u32, u32)> = BTreeMap::new();
+    for record in records {
+        if let Some(win) = record.win {
+            let (games, wins) = groups.entry(key(record)).or_default();
+            *games += 1;
+            *wins += u32::from(win);
+        }
+    }
+
+    groups
+        .into_iter()
+        .map(|(key, (games, wins))| (key, games, wins))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn game(champion_id: u32, position: &str, queue_id: u32, win: Option<bool>) -> GameRecord {
+        GameRecord {
+            champion_id,
+            position: position.to_owned(),
+            queue_id,
+            win,
+            ..Default::default()
+        }
+    }
+
+    fn games() -> Vec<GameRecord> {
+        vec![
+            game(145, "bottom", 420, Some(true)),
+            game(145, "bottom", 420, Some(false)),
+            game(145, "middle", 440, Some(true)),
+            game(64, "jungle", 420, Some(true)),
+            game(64, "jungle", 420, None),
+        ]
+    }
+
+    #[test]
+    fn empty_filter_matches_everything() {
+        assert!(games()
+            .iter()
+            .all(|record| Filter::default().matches(record)));
+    }
+
+    #[test]
+    fn filter_requires_every_set_field() {
+        let filter = Filter {
+            champion_id: Some(145),
+            position: Some("bottom".to_owned()),
+            queue_id: None,
+        };
+        let matched: Vec<GameRecord> = games()
+            .into_iter()
+            .filter(|record| filter.matches(record))
+            .collect();
+
+        assert_eq!(matched.len(), 2);
+        assert!(!Filter {
+            queue_id: Some(450),
+            ..Default::default()
+        }
+        .matches(&games()[0]));
+    }
+
+    #[test]
+    fn win_rates_group_by_key_and_skip_unknown_results() {
+        let games = games();
+
+        assert_eq!(
+            win_rates(&games, |record| record.champion_id),
+            vec![(64, 1, 1), (145, 3, 2)]
+        );
+        assert_eq!(
+            win_rates(&games, |record| record.position.clone()),
+            vec![
+                ("bottom".to_owned(), 2, 1),
+                ("jungle".to_owned(), 1, 1),
+                ("middle".to_owned(), 1, 1),
+            ]
+        );
+        assert!(win_rates(&games[4..], |record| record.queue_id).is_empty());
+    }
+}
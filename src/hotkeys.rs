@@ -0,0 +1,215 @@
+use serde::{Deserialize, Serialize};
+use std::sync::{Arc, Mutex};
+
+/// File the hotkey bindings are saved to.
+pub const HOTKEYS_PATH: &str = "./utils/hotkeys.json";
+
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+/// The `Hotkeys` struct holds the global hotkey bindings, written like `Ctrl+Alt+A`. Empty bindings are disabled.
+///
+/// ### Properties:
+/// * `toggle_auto_accept`: Toggles auto-accept.
+/// * `toggle_pick_ban`: Toggles auto-pick/ban.
+/// * `pause_all`: Pauses or resumes every automation.
+pub struct Hotkeys {
+    pub toggle_auto_accept: String,
+    pub toggle_pick_ban: String,
+    pub pause_all: String,
+}
+
+impl Default for Hotkeys {
+    fn default() -> Self {
+        Self {
+            toggle_auto_accept: "Ctrl+Alt+A".to_owned(),
+            toggle_pick_ban: "Ctrl+Alt+P".to_owned(),
+            pause_all: "Ctrl+Alt+S".to_owned(),
+        }
+    }
+}
+
+impl Hotkeys {
+    /// Reads the hotkeys file, the default bindings if it doesn't exist or can't be parsed.
+    pub fn load(path: &str) -> Self {
+        std::fs::read_to_string(path)
+            .ok()
+            .and_then(|data| serde_json::from_str(&data).ok())
+            .unwrap_or_default()
+    }
+
+    pub fn save(&self, path: &str) -> std::io::Result<()> {
+        std::fs::write(path, serde_json::to_string_pretty(self)?)
+    }
+
+    fn bindings(&self) -> [(HotkeyAction, &str); 3] {
+        [
+            (HotkeyAction::ToggleAutoAccept, &self.toggle_auto_accept),
+            (HotkeyAction::TogglePickBan, &self.toggle_pick_ban),
+            (HotkeyAction::PauseAll, &self.pause_all),
+        ]
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+/// Actions that can be bound to a global hotkey.
+pub enum HotkeyAction {
+    ToggleAutoAccept,
+    TogglePickBan,
+    PauseAll,
+}
+
+/// Parses a binding like `Ctrl+Shift+F2` into the modifiers and virtual key code `RegisterHotKey` expects.
+/// Keys can be a letter, a digit or `F1` to `F24`, and need at least one modifier so normal typing isn't caught.
+pub fn parse_hotkey(binding: &str) -> Option<(u32, u32)> {
+    use winapi::um::winuser::{MOD_ALT, MOD_CONTROL, MOD_SHIFT, MOD_WIN, VK_F1};
+
+    let mut modifiers = 0;
+    let mut key = None;
+    for part in binding.split('+').map(|part| part.trim().to_uppercase()) {
+        match part.as_str() {
+            "CTRL" | "CONTROL" => modifiers |= MOD_CONTROL as u32,
+            "ALT" => modifiers |= MOD_ALT as u32,
+            "SHIFT" => modifiers |= MOD_SHIFT as u32,
+            "WIN" => modifiers |= MOD_WIN as u32,
+            _ if key.is_some() => return None,
+            _ if part.len() == 1 && part.chars().all(|c| c.is_ascii_alphanumeric()) => {
+                key = Some(part.as_bytes()[0] as u32);
+            }
+            _ => {
+                let number: u32 = part.strip_prefix('F')?.parse().ok()?;
+                if !(1..=24).contains(&number) {
+                    return None;
+                }
+                key = Some(VK_F1 as u32 + number - 1);
+            }
+        }
+    }
+
+    if modifiers == 0 {
+        return None;
+    }
+    key.map(|key| (modifiers, key))
+}
+
+/// Registers the hotkeys and calls `on_hotkey` whenever one is pressed, from a thread of its own.
+/// Bindings are registered again whenever they change, bindings that can't be registered (invalid or
+/// already taken by another program) are listed in `status`.
+///
+/// ### Arguments:
+/// * `hotkeys`: The bindings, shared with the GUI.
+/// * `status`: Set to an error message while some bindings couldn't be registered.
+/// * `on_hotkey`: Called with the action of every hotkey pressed.
+pub fn listen(
+    hotkeys: Arc<Mutex<Hotkeys>>,
+    status: Arc<Mutex<Option<String>>>,
+    on_hotkey: impl Fn(HotkeyAction) + Send + 'static,
+) {
+    use std::ptr;
+    use winapi::um::winuser::{
+        PeekMessageW, RegisterHotKey, UnregisterHotKey, MOD_NOREPEAT, MSG, PM_REMOVE, WM_HOTKEY,
+    };
+
+    // Hotkeys are tied to the thread registering them, their messages are posted to its queue
+    std::thread::spawn(move || {
+        let mut registered: Option<Hotkeys> = None;
+        loop {
+            let wanted = hotkeys.lock().unwrap().clone();
+            if registered.as_ref() != Some(&wanted) {
+                let mut failed = Vec::new();
+                for (idx, (_, binding)) in wanted.bindings().iter().enumerate() {
+                    let id = idx as i32 + 1;
+                    unsafe {
+                        UnregisterHotKey(ptr::null_mut(), id);
+                    }
+                    if binding.trim().is_empty() {
+                        continue;
+                    }
+
+                    let ok = match parse_hotkey(binding) {
+                        Some((modifiers, key)) => unsafe {
+                            RegisterHotKey(
+                                ptr::null_mut(),
+                                id,
+                                modifiers | MOD_NOREPEAT as u32,
+                                key,
+                            ) != 0
+                        },
+                        None => false,
+                    };
+                    if !ok {
+                        failed.push(binding.to_string());
+                    }
+                }
+
+                *status.lock().unwrap() = if failed.is_empty() {
+                    None
+                } else {
+                    Some(format!("Couldn't register: {}", failed.join(", ")))
+                };
+                registered = Some(wanted);
+            }
+
+            let mut message: MSG = unsafe { std::mem::zeroed() };
+            while unsafe { PeekMessageW(&mut message, ptr::null_mut(), 0, 0, PM_REMOVE) } != 0 {
+                if message.message != WM_HOTKEY {
+                    continue;
+                }
+                if let Some((action, _)) = registered.as_ref().and_then(|hotkeys| {
+                    hotkeys
+                        .bindings()
+                        .get(message.wParam.wrapping_sub(1))
+                        .copied()
+                }) {
+                    on_hotkey(action);
+                }
+            }
+            std::thread::sleep(std::time::Duration::from_millis(50));
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use winapi::um::winuser::{MOD_ALT, MOD_CONTROL, MOD_SHIFT, MOD_WIN, VK_F1};
+
+    #[test]
+    fn parses_modifiers_and_letter_or_digit_keys() {
+        assert_eq!(
+            parse_hotkey("Ctrl+Alt+A"),
+            Some(((MOD_CONTROL | MOD_ALT) as u32, 'A' as u32))
+        );
+        assert_eq!(
+            parse_hotkey(" control + shift + 7 "),
+            Some(((MOD_CONTROL | MOD_SHIFT) as u32, '7' as u32))
+        );
+        assert_eq!(parse_hotkey("Win+p"), Some((MOD_WIN as u32, 'P' as u32)));
+    }
+
+    #[test]
+    fn parses_function_keys() {
+        assert_eq!(
+            parse_hotkey("Shift+F5"),
+            Some((MOD_SHIFT as u32, VK_F1 as u32 + 4))
+        );
+        assert_eq!(
+            parse_hotkey("Alt+F24"),
+            Some((MOD_ALT as u32, VK_F1 as u32 + 23))
+        );
+    }
+
+    #[test]
+    fn rejects_invalid_bindings() {
+        // No modifier, normal typing would be caught
+        assert_eq!(parse_hotkey("A"), None);
+        assert_eq!(parse_hotkey("F5"), None);
+        // Function keys out of range
+        assert_eq!(parse_hotkey("Ctrl+F25"), None);
+        assert_eq!(parse_hotkey("Ctrl+F0"), None);
+        // Two keys
+        assert_eq!(parse_hotkey("Ctrl+A+B"), None);
+        // Missing or unknown keys
+        assert_eq!(parse_hotkey("Ctrl+Alt"), None);
+        assert_eq!(parse_hotkey("Ctrl+Space"), None);
+        assert_eq!(parse_hotkey(""), None);
+    }
+}
@@ -0,0 +1,142 @@
+use std::collections::{HashMap, HashSet};
+use std::sync::{
+    atomic::{AtomicBool, Ordering},
+    Arc, Mutex,
+};
+
+/// Directory the champion icons downloaded from the LeagueClient are cached in.
+pub const CACHE_DIR: &str = "./utils/cache/champion-icons";
+
+/// Path of the cached icon of a champion.
+pub fn icon_path(champion_id: u32) -> String {
+    format!("{}/{}.png", CACHE_DIR, champion_id)
+}
+
+#[derive(Default)]
+/// The `ChampionIcons` struct holds the champion icons loaded as textures for the GUI.
+///
+/// ### Properties:
+/// * `textures`: The icons loaded so far, by champion id.
+/// * `requested`: Champions whose icon isn't cached yet, shared with the [`IconDownloader`] which downloads them.
+pub struct ChampionIcons {
+    textures: HashMap<u32, egui::TextureHandle>,
+    pub requested: Arc<Mutex<HashSet<u32>>>,
+}
+
+impl ChampionIcons {
+    /// Returns the texture of a champion icon, loading it from the cache the first time.
+    /// Icons that aren't cached are requested from the worker, `None` is returned until they are downloaded.
+    pub fn get(&mut self, ctx: &egui::Context, champion_id: u32) -> Option<egui::TextureId> {
+        if champion_id == 0 {
+            return None;
+        }
+        if let Some(texture) = self.textures.get(&champion_id) {
+            return Some(texture.id());
+        }
+
+        let mut requested = self.requested.lock().unwrap();
+        if requested.contains(&champion_id) {
+            return None;
+        }
+
+        let image = std::fs::read(icon_path(champion_id))
+            .ok()
+            .and_then(|bytes| egui_extras::image::load_image_bytes(&bytes).ok());
+        match image {
+            Some(image) => {
+                let texture = ctx.load_texture(
+                    format!("champion-icon-{}", champion_id),
+                    image,
+                    Default::default(),
+                );
+                let texture_id = texture.id();
+                self.textures.insert(champion_id, texture);
+                Some(texture_id)
+            }
+            None => {
+                requested.insert(champion_id);
+                None
+            }
+        }
+    }
+}
+
+/// Downloads the square icon of a champion from the LeagueClient game data and writes it to the cache.
+///
+/// ### Arguments:
+/// * `lcu`: The client the LeagueClient requests go through.
+/// * `champion_id`: The id of the champion.
+pub async fn download_icon(
+    lcu: &crate::lcu::LcuClient,
+    champion_id: u32,
+) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    let bytes = lcu
+        .get_bytes(&format!(
+            "/lol-game-data/assets/v1/champion-icons/{}.png",
+            champion_id
+        ))
+        .await?;
+
+    std::fs::create_dir_all(CACHE_DIR)?;
+    std::fs::write(icon_path(champion_id), bytes)?;
+
+    Ok(())
+}
+
+#[derive(Clone, Default)]
+/// The `IconDownloader` struct downloads the requested champion icons from a task of its own,
+/// so a burst of requests (e.g. typing in a suggestion box) doesn't hold up the worker.
+///
+/// ### Properties:
+/// * `requested`: The icons requested by the GUI, removed once downloaded.
+/// * `failed`: Icons that couldn't be downloaded, skipped until cleared.
+/// * `running`: Whether a download task is running, only one runs at a time.
+pub struct IconDownloader {
+    requested: Arc<Mutex<HashSet<u32>>>,
+    failed: Arc<Mutex<HashSet<u32>>>,
+    running: Arc<AtomicBool>,
+}
+
+impl IconDownloader {
+    pub fn new(requested: Arc<Mutex<HashSet<u32>>>) -> Self {
+        Self {
+            requested,
+            ..Default::default()
+        }
+    }
+
+    /// Lets the icons that failed be downloaded again.
+    pub fn clear_failed(&self) {
+        self.failed.lock().unwrap().clear();
+    }
+
+    /// Starts a task downloading the requested icons, unless one is already running or there's nothing to download.
+    pub fn start(&self, lcu: &crate::lcu::LcuClient) {
+        let pending: Vec<u32> = {
+            let failed = self.failed.lock().unwrap();
+            self.requested
+                .lock()
+                .unwrap()
+                .iter()
+                .filter(|champion_id| !failed.contains(champion_id))
+                .copied()
+                .collect()
+        };
+        if pending.is_empty() || self.running.swap(true, Ordering::SeqCst) {
+            return;
+        }
+
+        let downloader = self.clone();
+        let lcu = lcu.clone();
+        tokio::spawn(async move {
+            for champion_id in pending {
+                if download_icon(&lcu, champion_id).await.is_ok() {
+                    downloader.requested.lock().unwrap().remove(&champion_id);
+                } else {
+                    downloader.failed.lock().unwrap().insert(champion_id);
+                }
+            }
+            downloader.running.store(false, Ordering::SeqCst);
+        });
+    }
+}
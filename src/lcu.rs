@@ -0,0 +1,500 @@
+use http::{header::AUTHORIZATION, HeaderValue};
+use reqwest::{header, ClientBuilder};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, VecDeque};
+use std::io::Write;
+use std::sync::{Arc, Mutex};
+
+#[derive(Debug)]
+/// Errors returned by the LeagueClient requests.
+pub enum Error {
+    /// The request couldn't be sent or its response couldn't be read.
+    Request(reqwest::Error),
+    /// The LeagueClient answered with an error status code.
+    Status(u16),
+    /// The response didn't have the expected shape.
+    Parse(serde_json::Error),
+    /// The request can't be answered from the recording being replayed.
+    Replay(String),
+}
+
+impl std::fmt::Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::Request(error) => write!(f, "request failed: {}", error),
+            Error::Status(status) => write!(f, "LeagueClient answered with status {}", status),
+            Error::Parse(error) => write!(f, "unexpected response: {}", error),
+            Error::Replay(reason) => write!(f, "replay: {}", reason),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl From<reqwest::Error> for Error {
+    fn from(error: reqwest::Error) -> Self {
+        Error::Request(error)
+    }
+}
+
+impl From<serde_json::Error> for Error {
+    fn from(error: serde_json::Error) -> Self {
+        Error::Parse(error)
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone)]
+/// The `Exchange` struct is one request to the LeagueClient and its response, one line of a recording.
+///
+/// ### Properties:
+/// * `elapsed_ms`: When the request was sent, in milliseconds since the recording started.
+/// * `timestamp_ms`: When the request was sent, in milliseconds since the Unix epoch, `0` in older recordings.
+/// * `method`: The HTTP method of the request.
+/// * `path`: The path of the endpoint, e.g. `/lol-gameflow/v1/session`.
+/// * `request`: The JSON body sent, if there was one.
+/// * `status`: The status code of the response, `0` if the request couldn't be sent.
+/// * `response`: The JSON body of the response, `null` if it was empty.
+pub struct Exchange {
+    pub elapsed_ms: u64,
+    #[serde(default)]
+    pub timestamp_ms: u64,
+    pub method: String,
+    pub path: String,
+    pub request: Option<serde_json::Value>,
+    pub status: u16,
+    pub response: serde_json::Value,
+}
+
+#[derive(Clone)]
+/// The `Recording` struct is a file every exchange with the LeagueClient is appended to.
+/// It's kept across reconnections so a whole session ends up in the same file.
+pub struct Recording {
+    file: Arc<Mutex<std::fs::File>>,
+    started: std::time::Instant,
+}
+
+impl Recording {
+    pub fn create(path: &str) -> std::io::Result<Self> {
+        Ok(Self {
+            file: Arc::new(Mutex::new(std::fs::File::create(path)?)),
+            started: std::time::Instant::now(),
+        })
+    }
+
+    fn write(&self, exchange: &Exchange) {
+        if let Ok(line) = serde_json::to_string(exchange) {
+            let _ = writeln!(self.file.lock().unwrap(), "{}", line);
+        }
+    }
+}
+
+/// The recorded exchanges still to be replayed, in recording order for each method and path.
+/// Requests for state (GET) get the last exchange recorded by the time they're replayed at, so the
+/// state changes as fast as it did while recording.
+struct Replay {
+    pending: HashMap<(String, String), VecDeque<Exchange>>,
+    last: HashMap<(String, String), Exchange>,
+    /// Set on the first request, the recording is replayed relative to it.
+    started: Option<std::time::Instant>,
+    /// When the recording started, in milliseconds since the Unix epoch, `None` for older recordings.
+    recorded_at_ms: Option<u64>,
+}
+
+impl Replay {
+    fn new(exchanges: Vec<Exchange>) -> Self {
+        let recorded_at_ms = exchanges
+            .iter()
+            .find(|exchange| exchange.timestamp_ms != 0)
+            .map(|exchange| exchange.timestamp_ms.saturating_sub(exchange.elapsed_ms));
+        let mut pending: HashMap<(String, String), VecDeque<Exchange>> = HashMap::new();
+        for exchange in exchanges {
+            pending
+                .entry((exchange.method.clone(), exchange.path.clone()))
+                .or_default()
+                .push_back(exchange);
+        }
+
+        Self {
+            pending,
+            last: HashMap::new(),
+            started: None,
+            recorded_at_ms,
+        }
+    }
+
+    /// The time since the replay started, starting it if this is the first request.
+    fn elapsed_ms(&mut self) -> u64 {
+        self.started
+            .get_or_insert_with(std::time::Instant::now)
+            .elapsed()
+            .as_millis() as u64
+    }
+
+    /// The clock of the recording `elapsed_ms` after the replay started, the wall clock for older recordings.
+    fn now_ms(&self, elapsed_ms: u64) -> u64 {
+        match self.recorded_at_ms {
+            Some(recorded_at_ms) => recorded_at_ms + elapsed_ms,
+            None => crate::draft::now_ms(),
+        }
+    }
+}
+
+#[derive(Clone)]
+enum Mode {
+    Live(Option<Recording>),
+    Replay(Arc<Mutex<Replay>>),
+}
+
+#[derive(Clone)]
+/// The `LcuClient` struct sends the requests to the LeagueClient, every request of the worker goes through it
+/// so sessions can be recorded and replayed later without the LeagueClient running.
+///
+/// ### Properties:
+/// * `rest_client`: The client holding the LeagueClient authorization header.
+/// * `port`: The port the LeagueClient is listening on.
+/// * `mode`: Whether requests are sent live (and possibly recorded) or answered from a recording.
+pub struct LcuClient {
+    rest_client: reqwest::Client,
+    port: u32,
+    mode: Mode,
+}
+
+impl LcuClient {
+    /// Creates a client for the LeagueClient described by a lockfile.
+    ///
+    /// ### Arguments:
+    /// * `port`: The port the LeagueClient is listening on.
+    /// * `b64_auth`: The base64 encoded `riot:password` credentials.
+    /// * `recording`: The recording every exchange is written to, if any.
+    pub fn connect(port: u32, b64_auth: &str, recording: Option<Recording>) -> Self {
+        let auth_header = HeaderValue::from_str(format!("Basic {}", b64_auth).as_str()).unwrap();
+        let cert =
+            reqwest::Certificate::from_pem(include_bytes!("../utils/riotgames.pem")).unwrap();
+        let mut headers = header::HeaderMap::new();
+
+        headers.insert(AUTHORIZATION, auth_header);
+        let rest_client = ClientBuilder::new()
+            .add_root_certificate(cert)
+            .default_headers(headers)
+            .build()
+            .unwrap();
+
+        Self {
+            rest_client,
+            port,
+            mode: Mode::Live(recording),
+        }
+    }
+
+    /// Creates a client answering every request from a recording instead of the LeagueClient.
+    pub fn replay(path: &str) -> std::io::Result<Self> {
+        let exchanges = std::fs::read_to_string(path)?
+            .lines()
+            .filter_map(|line| serde_json::from_str::<Exchange>(line).ok())
+            .collect();
+
+        Ok(Self {
+            rest_client: reqwest::Client::new(),
+            port: 0,
+            mode: Mode::Replay(Arc::new(Mutex::new(Replay::new(exchanges)))),
+        })
+    }
+
+    /// The current time as a Unix timestamp in milliseconds, as seen by the LeagueClient.
+    /// Replays follow the clock of the recording, so the champion select timers count down as they did while recording.
+    pub fn now_ms(&self) -> u64 {
+        match &self.mode {
+            Mode::Live(_) => crate::draft::now_ms(),
+            Mode::Replay(replay) => {
+                let mut replay = replay.lock().unwrap();
+                let elapsed_ms = replay.elapsed_ms();
+                replay.now_ms(elapsed_ms)
+            }
+        }
+    }
+
+    /// Whether every recorded exchange has been replayed.
+    pub fn replay_finished(&self) -> bool {
+        match &self.mode {
+            Mode::Live(_) => false,
+            Mode::Replay(replay) => replay
+                .lock()
+                .unwrap()
+                .pending
+                .values()
+                .all(|exchanges| exchanges.is_empty()),
+        }
+    }
+
+    pub async fn get(&self, path: &str) -> Result<serde_json::Value, Error> {
+        self.request(reqwest::Method::GET, path, None).await
+    }
+
+    pub async fn post(
+        &self,
+        path: &str,
+        body: Option<&serde_json::Value>,
+    ) -> Result<serde_json::Value, Error> {
+        self.request(reqwest::Method::POST, path, body).await
+    }
+
+    pub async fn put(
+        &self,
+        path: &str,
+        body: &serde_json::Value,
+    ) -> Result<serde_json::Value, Error> {
+        self.request(reqwest::Method::PUT, path, Some(body)).await
+    }
+
+    pub async fn patch(
+        &self,
+        path: &str,
+        body: &serde_json::Value,
+    ) -> Result<serde_json::Value, Error> {
+        self.request(reqwest::Method::PATCH, path, Some(body)).await
+    }
+
+    /// Downloads a binary asset. Assets aren't recorded, so they can't be replayed either.
+    pub async fn get_bytes(&self, path: &str) -> Result<Vec<u8>, Error> {
+        if let Mode::Replay(_) = self.mode {
+            return Err(Error::Replay(format!("assets aren't recorded ({})", path)));
+        }
+
+        let bytes = self
+            .rest_client
+            .get(format!("https://127.0.0.1:{}{}", self.port, path))
+            .send()
+            .await?
+            .error_for_status()?
+            .bytes()
+            .await?;
+
+        Ok(bytes.to_vec())
+    }
+
+    /// Sends a request with an optional JSON body and returns the JSON response, `null` if it was empty.
+    async fn request(
+        &self,
+        method: reqwest::Method,
+        path: &str,
+        body: Option<&serde_json::Value>,
+    ) -> Result<serde_json::Value, Error> {
+        let recording = match &self.mode {
+            Mode::Replay(replay) => {
+                let mut replay = replay.lock().unwrap();
+                let elapsed_ms = replay.elapsed_ms();
+                return replay_request(&mut replay, method, path, elapsed_ms);
+            }
+            Mode::Live(recording) => recording,
+        };
+
+        let mut request = self.rest_client.request(
+            method.clone(),
+            format!("https://127.0.0.1:{}{}", self.port, path),
+        );
+        if let Some(body) = body {
+            request = request.json(body);
+        }
+        let sent_at = recording.as_ref().map(|recording| {
+            (
+                recording.started.elapsed().as_millis() as u64,
+                crate::draft::now_ms(),
+            )
+        });
+
+        let result = match request.send().await {
+            Ok(response) => {
+                let status = response.status().as_u16();
+                response.text().await.map(|text| {
+                    let json = if text.is_empty() {
+                        serde_json::Value::Null
+                    } else {
+                        serde_json::from_str(&text).unwrap_or(serde_json::Value::String(text))
+                    };
+                    (status, json)
+                })
+            }
+            Err(error) => Err(error),
+        };
+
+        if let (Some(recording), Some((elapsed_ms, timestamp_ms))) = (recording, sent_at) {
+            let (status, response) = match &result {
+                Ok((status, json)) => (*status, json.clone()),
+                Err(_) => (0, serde_json::Value::Null),
+            };
+            recording.write(&Exchange {
+                elapsed_ms,
+                timestamp_ms,
+                method: method.to_string(),
+                path: path.to_owned(),
+                request: body.cloned(),
+                status,
+                response,
+            });
+        }
+
+        let (status, json) = result?;
+        if (200..300).contains(&status) {
+            Ok(json)
+        } else {
+            Err(Error::Status(status))
+        }
+    }
+}
+
+/// Answers a request from the recording, `elapsed_ms` after the replay started.
+/// GETs get the last exchange recorded by then for the same path, or the first one if it was only requested later.
+/// Actions get the next recorded exchange for the same method and path, actions that were never recorded succeed
+/// without doing anything, so the worker can diverge from the recording.
+fn replay_request(
+    replay: &mut Replay,
+    method: reqwest::Method,
+    path: &str,
+    elapsed_ms: u64,
+) -> Result<serde_json::Value, Error> {
+    let key = (method.to_string(), path.to_owned());
+    let pending = replay.pending.entry(key.clone()).or_default();
+    let exchange = if method == reqwest::Method::GET {
+        while let Some(exchange) = pending.front() {
+            if exchange.elapsed_ms > elapsed_ms && replay.last.contains_key(&key) {
+                break;
+            }
+            let exchange = pending.pop_front().unwrap();
+            replay.last.insert(key.clone(), exchange);
+        }
+        match replay.last.get(&key) {
+            Some(exchange) => exchange.clone(),
+            None => {
+                return Err(Error::Replay(format!(
+                    "{} {} was never recorded",
+                    method, path
+                )))
+            }
+        }
+    } else {
+        match pending.pop_front() {
+            Some(exchange) => exchange,
+            None => return Ok(serde_json::Value::Null),
+        }
+    };
+
+    match exchange.status {
+        0 => Err(Error::Replay(format!(
+            "{} {} failed while recording",
+            method, path
+        ))),
+        200..=299 => Ok(exchange.response),
+        status => Err(Error::Status(status)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn exchange(elapsed_ms: u64, method: &str, path: &str, phase: &str) -> Exchange {
+        Exchange {
+            elapsed_ms,
+            timestamp_ms: 0,
+            method: method.to_owned(),
+            path: path.to_owned(),
+            request: None,
+            status: 200,
+            response: serde_json::json!({ "phase": phase }),
+        }
+    }
+
+    fn phase(replay: &mut Replay, elapsed_ms: u64) -> serde_json::Value {
+        replay_request(replay, reqwest::Method::GET, "/session", elapsed_ms).unwrap()["phase"]
+            .clone()
+    }
+
+    #[test]
+    fn gets_follow_the_recorded_timing() {
+        let mut replay = Replay::new(vec![
+            exchange(0, "GET", "/session", "Lobby"),
+            exchange(1000, "GET", "/session", "Matchmaking"),
+            exchange(1500, "GET", "/session", "ReadyCheck"),
+            exchange(5000, "GET", "/session", "ChampSelect"),
+        ]);
+
+        assert_eq!(phase(&mut replay, 0), "Lobby");
+        assert_eq!(phase(&mut replay, 900), "Lobby");
+        assert_eq!(phase(&mut replay, 1600), "ReadyCheck");
+        assert_eq!(phase(&mut replay, 4000), "ReadyCheck");
+        assert_eq!(phase(&mut replay, 6000), "ChampSelect");
+        assert_eq!(phase(&mut replay, 9000), "ChampSelect");
+        assert!(replay
+            .pending
+            .values()
+            .all(|exchanges| exchanges.is_empty()));
+    }
+
+    #[test]
+    fn first_get_answers_before_its_recorded_time() {
+        let mut replay = Replay::new(vec![
+            exchange(3000, "GET", "/session", "ChampSelect"),
+            exchange(8000, "GET", "/session", "InProgress"),
+        ]);
+
+        assert_eq!(phase(&mut replay, 0), "ChampSelect");
+        assert_eq!(phase(&mut replay, 5000), "ChampSelect");
+        assert!(matches!(
+            replay_request(&mut replay, reqwest::Method::GET, "/other", 0),
+            Err(Error::Replay(_))
+        ));
+    }
+
+    #[test]
+    fn actions_are_replayed_in_order() {
+        let mut replay = Replay::new(vec![
+            exchange(0, "POST", "/accept", "first"),
+            exchange(9000, "POST", "/accept", "second"),
+        ]);
+        let mut post = || {
+            replay_request(&mut replay, reqwest::Method::POST, "/accept", 0).unwrap()["phase"]
+                .clone()
+        };
+
+        assert_eq!(post(), "first");
+        assert_eq!(post(), "second");
+        assert_eq!(post(), serde_json::Value::Null);
+    }
+
+    #[test]
+    fn late_lock_follows_the_recorded_clock() {
+        let recorded_at_ms = 1_700_000_000_000;
+        let session = |elapsed_ms: u64, time_left: f64| Exchange {
+            elapsed_ms,
+            timestamp_ms: recorded_at_ms + elapsed_ms,
+            method: "GET".to_owned(),
+            path: "/lol-champ-select/v1/session".to_owned(),
+            request: None,
+            status: 200,
+            response: serde_json::json!({
+                "timer": {
+                    "phase": "BAN_PICK",
+                    "adjustedTimeLeftInPhase": time_left,
+                    "internalNowInEpochMs": recorded_at_ms + elapsed_ms
+                }
+            }),
+        };
+        let mut replay = Replay::new(vec![session(1000, 30_000.0), session(11_000, 20_000.0)]);
+        let mut lock_due = |elapsed_ms: u64| {
+            let session = replay_request(
+                &mut replay,
+                reqwest::Method::GET,
+                "/lol-champ-select/v1/session",
+                elapsed_ms,
+            )
+            .unwrap();
+            crate::draft::late_lock_due(&session["timer"], replay.now_ms(elapsed_ms), 5)
+        };
+
+        // Against the wall clock the recorded timer would already be over
+        assert!(!lock_due(1000));
+        assert!(!lock_due(10_000));
+        assert!(!lock_due(25_999));
+        assert!(lock_due(26_000));
+    }
+}
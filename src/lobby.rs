@@ -0,0 +1,203 @@
+use serde::{Deserialize, Serialize};
+
+/// Leaves the post game screen and returns the player to their previous lobby.
+///
+/// ### Arguments:
+/// * `lcu`: The client the LeagueClient requests go through.
+pub async fn play_again(lcu: &crate::lcu::LcuClient) -> Result<(), crate::lcu::Error> {
+    lcu.post("/lol-lobby/v2/play-again", None).await?;
+
+    Ok(())
+}
+
+/// Starts the matchmaking search for the current lobby. Only works if the player is the lobby leader.
+///
+/// ### Arguments:
+/// * `lcu`: The client the LeagueClient requests go through.
+pub async fn start_matchmaking(lcu: &crate::lcu::LcuClient) -> Result<(), crate::lcu::Error> {
+    lcu.post("/lol-lobby/v2/lobby/matchmaking/search", None)
+        .await?;
+
+    Ok(())
+}
+
+/// Queues a lobby can be created for, as `(queueId, name)` pairs.
+pub const QUEUES: [(u32, &str); 8] = [
+    (400, "Normal Draft"),
+    (420, "Ranked Solo/Duo"),
+    (430, "Normal Blind"),
+    (440, "Ranked Flex"),
+    (450, "ARAM"),
+    (830, "Co-op vs. AI Intro"),
+    (840, "Co-op vs. AI Beginner"),
+    (850, "Co-op vs. AI Intermediate"),
+];
+
+/// Positions accepted by the position preferences endpoint, as `(position, name)` pairs.
+pub const POSITIONS: [(&str, &str); 6] = [
+    ("TOP", "Top"),
+    ("JUNGLE", "Jungle"),
+    ("MIDDLE", "Mid"),
+    ("BOTTOM", "Bot"),
+    ("UTILITY", "Support"),
+    ("FILL", "Fill"),
+];
+
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+/// The `LobbySettings` struct holds everything needed to set up a lobby from the GUI.
+///
+/// ### Properties:
+/// * `queue_id`: The id of the queue the lobby is created for, see [`QUEUES`].
+/// * `first_position`: The primary position, see [`POSITIONS`].
+/// * `second_position`: The secondary position, ignored if the primary position is `FILL`.
+/// * `start_search`: Whether matchmaking is started right after the lobby is set up.
+pub struct LobbySettings {
+    pub queue_id: u32,
+    pub first_position: String,
+    pub second_position: String,
+    pub start_search: bool,
+}
+
+impl Default for LobbySettings {
+    fn default() -> Self {
+        Self {
+            queue_id: 400,
+            first_position: "MIDDLE".to_owned(),
+            second_position: "FILL".to_owned(),
+            start_search: false,
+        }
+    }
+}
+
+impl LobbySettings {
+    /// Whether the queue of the lobby asks for position preferences.
+    pub fn uses_positions(&self) -> bool {
+        queue_uses_positions(self.queue_id)
+    }
+
+    /// Whether the roles can be sent, the secondary position is ignored when filling.
+    pub fn roles_valid(&self) -> bool {
+        !self.uses_positions()
+            || self.first_position == "FILL"
+            || self.first_position != self.second_position
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+/// Lobby actions requested from the GUI, handled by the worker on its next iteration.
+pub enum LobbyAction {
+    Create,
+    SetPositions,
+}
+
+/// Only draft and ranked queues ask for position preferences.
+pub fn queue_uses_positions(queue_id: u32) -> bool {
+    matches!(queue_id, 400 | 420 | 440)
+}
+
+/// Returns the display name of a queue id, or the id itself if it isn't one of the known [`QUEUES`].
+pub fn queue_name(queue_id: u32) -> String {
+    QUEUES
+        .iter()
+        .find(|(id, _)| *id == queue_id)
+        .map(|(_, name)| name.to_string())
+        .unwrap_or_else(|| format!("Queue {}", queue_id))
+}
+
+/// Returns the display name of a position, or the position itself if it isn't one of the known [`POSITIONS`].
+pub fn position_name(position: &str) -> &str {
+    POSITIONS
+        .iter()
+        .find(|(key, _)| *key == position)
+        .map(|(_, name)| *name)
+        .unwrap_or(position)
+}
+
+/// Creates a new lobby for the given queue, leaving the current one if there is any.
+///
+/// ### Arguments:
+/// * `lcu`: The client the LeagueClient requests go through.
+/// * `queue_id`: The id of the queue the lobby is created for.
+pub async fn create_lobby(
+    lcu: &crate::lcu::LcuClient,
+    queue_id: u32,
+) -> Result<(), crate::lcu::Error> {
+    lcu.post(
+        "/lol-lobby/v2/lobby",
+        Some(&serde_json::json!({ "queueId": queue_id })),
+    )
+    .await?;
+
+    Ok(())
+}
+
+/// Sets the primary and secondary positions of the player in the current lobby.
+///
+/// ### Arguments:
+/// * `lcu`: The client the LeagueClient requests go through.
+/// * `first_position`: The primary position, one of the keys in [`POSITIONS`].
+/// * `second_position`: The secondary position, one of the keys in [`POSITIONS`].
+pub async fn set_position_preferences(
+    lcu: &crate::lcu::LcuClient,
+    first_position: &str,
+    second_position: &str,
+) -> Result<(), crate::lcu::Error> {
+    lcu.put(
+        "/lol-lobby/v2/lobby/members/localMember/position-preferences",
+        &position_preferences(first_position, second_position),
+    )
+    .await?;
+
+    Ok(())
+}
+
+/// Builds the body of the position preferences request.
+fn position_preferences(first_position: &str, second_position: &str) -> serde_json::Value {
+    // The client expects UNSELECTED as the second position when filling
+    let second_position = if first_position == "FILL" {
+        "UNSELECTED"
+    } else {
+        second_position
+    };
+
+    serde_json::json!({
+        "firstPreference": first_position,
+        "secondPreference": second_position
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn settings(queue_id: u32, first_position: &str, second_position: &str) -> LobbySettings {
+        LobbySettings {
+            queue_id,
+            first_position: first_position.to_owned(),
+            second_position: second_position.to_owned(),
+            start_search: false,
+        }
+    }
+
+    #[test]
+    fn filling_leaves_the_second_position_unselected() {
+        assert_eq!(
+            position_preferences("FILL", "FILL"),
+            serde_json::json!({ "firstPreference": "FILL", "secondPreference": "UNSELECTED" })
+        );
+        assert_eq!(
+            position_preferences("MIDDLE", "FILL"),
+            serde_json::json!({ "firstPreference": "MIDDLE", "secondPreference": "FILL" })
+        );
+    }
+
+    #[test]
+    fn roles_need_to_differ_unless_filling() {
+        assert!(LobbySettings::default().roles_valid());
+        assert!(settings(400, "FILL", "FILL").roles_valid());
+        assert!(settings(420, "TOP", "JUNGLE").roles_valid());
+        assert!(!settings(420, "TOP", "TOP").roles_valid());
+        // Queues without positions ignore the roles
+        assert!(settings(450, "TOP", "TOP").roles_valid());
+    }
+}
@@ -1,17 +1,35 @@
 #![cfg_attr(not(debug_assertions), windows_subsystem = "windows")] // hides the terminal
 
+mod aram;
+mod counters;
+mod draft;
+mod history;
+mod hotkeys;
+mod icons;
+mod lcu;
+mod lobby;
+mod notifications;
+mod ownership;
+mod profiles;
+mod recorder;
+mod search;
+mod skins;
+mod sounds;
+mod trades;
+mod tray;
+mod webhooks;
+
 use eframe::egui;
 use egui::{vec2, TextEdit};
 use egui_extras::{self, RetainedImage};
-use http::{header::AUTHORIZATION, HeaderValue};
 use league_client_connector::LeagueClientConnector;
-use reqwest::{header, ClientBuilder};
+use raw_window_handle::{HasRawWindowHandle, RawWindowHandle};
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::error::Error;
 use std::io::Write;
 use std::sync::{
-    atomic::{AtomicBool, Ordering},
+    atomic::{AtomicBool, AtomicIsize, Ordering},
     Arc, Mutex,
 };
 
@@ -19,13 +37,55 @@ pub struct GUI {
     pick_ban_selection: Arc<AtomicBool>,
     rune_page_selection: Arc<AtomicBool>,
     auto_accept: Arc<AtomicBool>,
+    auto_requeue: Arc<AtomicBool>,
+    requeue_delay: Arc<Mutex<u64>>,
+    requeue_max_games: Arc<Mutex<u32>>,
+    lobby_settings: Arc<Mutex<lobby::LobbySettings>>,
+    lobby_action: Arc<Mutex<Option<lobby::LobbyAction>>>,
     spell_selection: Arc<AtomicBool>,
     pick_text: String,
     ban_text: String,
     text: String,
     champion_picks: Arc<Mutex<Vec<(u32, String)>>>,
-    ban_picks: Arc<Mutex<Option<(u32, String)>>>,
-    champions: Vec<Champion>,
+    ban_picks: Arc<Mutex<Vec<(u32, String)>>>,
+    respect_ally_hovers: Arc<AtomicBool>,
+    counter_pick: Arc<AtomicBool>,
+    counters_error: Arc<Mutex<Option<String>>>,
+    late_lock: Arc<AtomicBool>,
+    late_lock_seconds: Arc<Mutex<u64>>,
+    aram_bench_swap: Arc<AtomicBool>,
+    aram_reroll: Arc<AtomicBool>,
+    aram_wishlist: Arc<Mutex<Vec<(u32, String)>>>,
+    aram_text: String,
+    auto_trades: Arc<AtomicBool>,
+    auto_pick_order_swaps: Arc<AtomicBool>,
+    skin_choices: Arc<Mutex<HashMap<u32, skins::SkinChoice>>>,
+    champion_skins: Arc<Mutex<HashMap<u32, Vec<skins::Skin>>>>,
+    champion_availability: Arc<Mutex<HashMap<u32, ownership::Availability>>>,
+    champion_search: search::ChampionSearch,
+    champion_icons: icons::ChampionIcons,
+    current_champion: Arc<Mutex<Option<u32>>>,
+    champ_select: Arc<Mutex<Option<(draft::Draft, std::time::Instant)>>>,
+    summoner_spells: Vec<SummonerSpell>,
+    match_history: Arc<Mutex<Vec<history::GameRecord>>>,
+    history_filter: history::Filter,
+    record_drafts: Arc<AtomicBool>,
+    export_status: Option<String>,
+    profiles: Arc<Mutex<profiles::Profiles>>,
+    profile_name: String,
+    profile_import_text: String,
+    profile_status: Option<String>,
+    rune_page_request: Arc<Mutex<Option<String>>>,
+    paused: Arc<AtomicBool>,
+    minimize_to_tray: Arc<AtomicBool>,
+    main_window: Arc<AtomicIsize>,
+    hotkeys: Arc<Mutex<hotkeys::Hotkeys>>,
+    hotkey_text: hotkeys::Hotkeys,
+    hotkey_status: Arc<Mutex<Option<String>>>,
+    notifier: notifications::Notifier,
+    sounds: sounds::SoundPlayer,
+    webhooks: webhooks::Webhooks,
+    webhook_text: webhooks::WebhookSettings,
     gameflow_status: Arc<Mutex<String>>,
     update: Arc<AtomicBool>,
     images: HashMap<String, RetainedImage>,
@@ -44,6 +104,7 @@ pub struct GUI {
     clear_label_timer: Option<std::time::Instant>,
     pick_not_found_label_timer: Option<std::time::Instant>,
     ban_not_found_label_timer: Option<std::time::Instant>,
+    aram_not_found_label_timer: Option<std::time::Instant>,
 }
 
 #[derive(Debug, Deserialize, Serialize, Clone)]
@@ -73,8 +134,11 @@ struct Champion {
 /// is currently in progress or not.
 /// * `r#type`: The property "r#type" is a string that represents the type of action response data. The
 /// "r#" prefix is used to escape the reserved keyword "type" in Rust.
+/// * `championId`: The id of the champion currently hovered or locked by the action, `0` if there is none.
 struct ActionResponseData {
     actorCellId: i32,
+    #[serde(default)]
+    championId: u32,
     completed: bool,
     id: i32,
     isInProgress: bool,
@@ -88,6 +152,10 @@ struct MyTeamData {
     assignedPosition: String,
     spell1Id: u32,
     spell2Id: u32,
+    #[serde(default)]
+    championPickIntent: u32,
+    #[serde(default)]
+    championId: u32,
 }
 
 #[derive(Deserialize, Debug)]
@@ -124,6 +192,16 @@ impl GUI {
             std::fs::read_to_string("./utils/champions.json").expect("Failed to read file");
         let champions: Vec<Champion> =
             serde_json::from_str(&json_data).expect("Failed to parse JSON");
+        let spells_data =
+            std::fs::read_to_string("./utils/summoner_spells.json").expect("Failed to read file");
+        let summoner_spells: Vec<SummonerSpell> =
+            serde_json::from_str(&spells_data).expect("Failed to parse JSON");
+        let champion_search = search::ChampionSearch::new(
+            champions
+                .iter()
+                .map(|champion| (champion.id, champion.name.as_str())),
+            &search::ChampionSearch::load_aliases("./utils/champion_aliases.json"),
+        );
 
         let mut images: HashMap<String, RetainedImage> = HashMap::new();
 
@@ -146,19 +224,66 @@ impl GUI {
         images.insert(smite_img.0, smite_img.1);
         images.insert(teleport_img.0, teleport_img.1);
 
-        Self {
+        let gui = Self {
             pick_ban_selection,
             rune_page_selection,
             auto_accept,
+            auto_requeue: Arc::new(AtomicBool::new(false)),
+            requeue_delay: Arc::new(Mutex::new(5)),
+            requeue_max_games: Arc::new(Mutex::new(0)),
+            lobby_settings: Arc::new(Mutex::new(lobby::LobbySettings::default())),
+            lobby_action: Arc::new(Mutex::new(None)),
             pick_text: String::new().to_owned(),
             ban_text: String::new().to_owned(),
             champion_picks: Arc::new(Mutex::new(Vec::new())),
-            ban_picks: Arc::new(Mutex::new(None)),
+            ban_picks: Arc::new(Mutex::new(Vec::new())),
+            respect_ally_hovers: Arc::new(AtomicBool::new(true)),
+            counter_pick: Arc::new(AtomicBool::new(false)),
+            counters_error: Arc::new(Mutex::new(None)),
+            late_lock: Arc::new(AtomicBool::new(false)),
+            late_lock_seconds: Arc::new(Mutex::new(5)),
+            aram_bench_swap: Arc::new(AtomicBool::new(false)),
+            aram_reroll: Arc::new(AtomicBool::new(false)),
+            aram_wishlist: Arc::new(Mutex::new(Vec::new())),
+            aram_text: String::new(),
+            auto_trades: Arc::new(AtomicBool::new(false)),
+            auto_pick_order_swaps: Arc::new(AtomicBool::new(false)),
+            skin_choices: Arc::new(Mutex::new(HashMap::new())),
+            champion_skins: Arc::new(Mutex::new(HashMap::new())),
+            champion_availability: Arc::new(Mutex::new(HashMap::new())),
             clear_label_timer: None,
             pick_not_found_label_timer: None,
             ban_not_found_label_timer: None,
+            aram_not_found_label_timer: None,
             connection_status,
-            champions,
+            champion_search,
+            champion_icons: icons::ChampionIcons::default(),
+            current_champion: Arc::new(Mutex::new(None)),
+            champ_select: Arc::new(Mutex::new(None)),
+            summoner_spells,
+            match_history: Arc::new(Mutex::new(history::load_history(history::HISTORY_PATH))),
+            history_filter: history::Filter::default(),
+            record_drafts: Arc::new(AtomicBool::new(false)),
+            export_status: None,
+            profiles: Arc::new(Mutex::new(profiles::Profiles::load(
+                profiles::PROFILES_PATH,
+            ))),
+            profile_name: String::new(),
+            profile_import_text: String::new(),
+            profile_status: None,
+            rune_page_request: Arc::new(Mutex::new(None)),
+            paused: Arc::new(AtomicBool::new(false)),
+            minimize_to_tray: Arc::new(AtomicBool::new(false)),
+            main_window: Arc::new(AtomicIsize::new(0)),
+            hotkeys: Arc::new(Mutex::new(hotkeys::Hotkeys::load(hotkeys::HOTKEYS_PATH))),
+            hotkey_text: hotkeys::Hotkeys::load(hotkeys::HOTKEYS_PATH),
+            hotkey_status: Arc::new(Mutex::new(None)),
+            notifier: notifications::Notifier::load(notifications::NOTIFICATIONS_PATH),
+            sounds: sounds::SoundPlayer::start(sounds::SoundSettings::load(sounds::SOUNDS_PATH)),
+            webhooks: webhooks::Webhooks::new(webhooks::WebhookSettings::load(
+                webhooks::WEBHOOKS_PATH,
+            )),
+            webhook_text: webhooks::WebhookSettings::load(webhooks::WEBHOOKS_PATH),
             text: String::new().to_owned(),
             gameflow_status: Arc::new(Mutex::new(String::new())),
             update_status: Arc::new(Mutex::new(String::new())),
@@ -173,12 +298,71 @@ impl GUI {
             spell_selection: summoner_spell_selection,
             assigned_role: Arc::new(Mutex::new(None)),
             active_tab: 0,
+        };
+
+        // The settings are restored from the profile that was active when the app was closed
+        if let Some(profile) = gui.profiles.lock().unwrap().active() {
+            gui.live_settings().apply(profile);
+        }
+
+        gui
+    }
+
+    /// The settings a profile is made of, shared with the worker so it can switch profiles too.
+    fn live_settings(&self) -> profiles::LiveSettings {
+        profiles::LiveSettings {
+            auto_accept: Arc::clone(&self.auto_accept),
+            pick_ban_selection: Arc::clone(&self.pick_ban_selection),
+            champion_picks: Arc::clone(&self.champion_picks),
+            ban_picks: Arc::clone(&self.ban_picks),
+            spell_selection: Arc::clone(&self.spell_selection),
+            spell1: Arc::clone(&self.selected_image1),
+            spell2: Arc::clone(&self.selected_image2),
+            lobby_settings: Arc::clone(&self.lobby_settings),
+            rune_page_request: Arc::clone(&self.rune_page_request),
+        }
+    }
+
+    /// Applies a profile change requested from the top bar and saves the profiles.
+    fn handle_profile_action(&self, action: profiles::ProfileAction) {
+        let settings = self.live_settings();
+        let mut profiles = self.profiles.lock().unwrap();
+
+        match action {
+            profiles::ProfileAction::Switch(name) => {
+                if let Some(profile) = profiles.get(&name) {
+                    settings.apply(profile);
+                    profiles.active = Some(name);
+                }
+            }
+            profiles::ProfileAction::Create(name) => {
+                profiles.profiles.push(settings.to_profile(&name));
+                profiles.active = Some(name);
+            }
+            profiles::ProfileAction::Save => {
+                let active = profiles.active.clone();
+                if let Some(profile) = active.and_then(|name| profiles.get_mut(&name)) {
+                    settings.capture(profile);
+                }
+            }
+            profiles::ProfileAction::Delete => {
+                if let Some(name) = profiles.active.take() {
+                    profiles.profiles.retain(|profile| profile.name != name);
+                }
+            }
         }
+
+        let _ = profiles.save(profiles::PROFILES_PATH);
     }
 }
 
 impl eframe::App for GUI {
     fn update(&mut self, ctx: &egui::Context, frame: &mut eframe::Frame) {
+        // The tray shows and hides the window through its handle
+        if let RawWindowHandle::Win32(handle) = frame.raw_window_handle() {
+            self.main_window
+                .store(handle.hwnd as isize, Ordering::SeqCst);
+        }
         let pick_ban_selection = self.pick_ban_selection.load(Ordering::SeqCst);
         if let Some(timer) = self.clear_label_timer {
             let elapsed = timer.elapsed();
@@ -198,14 +382,23 @@ impl eframe::App for GUI {
                 self.ban_not_found_label_timer = None;
             }
         }
+        if let Some(timer) = self.aram_not_found_label_timer {
+            let elapsed = timer.elapsed();
+            if elapsed.as_secs_f32() > 1.5 {
+                self.aram_not_found_label_timer = None;
+            }
+        }
         let mut champion_picks = self.champion_picks.lock().unwrap();
         let mut ban_picks = self.ban_picks.lock().unwrap();
+        let champion_availability = self.champion_availability.lock().unwrap().clone();
         let connection_status = self.connection_status.lock().unwrap();
         let gameflow_status = self.gameflow_status.lock().unwrap();
         let mut selected_image1 = self.selected_image1.lock().unwrap();
         let mut selected_image2 = self.selected_image2.lock().unwrap();
         let update_status = self.update_status.lock().unwrap().clone();
         let current_version = self.current_version.lock().unwrap().clone();
+        // Applied after the frame is drawn, profiles replace settings that are locked while drawing
+        let mut profile_action: Option<profiles::ProfileAction> = None;
 
         egui::TopBottomPanel::top("top panel").show(ctx, |ui| {
             egui::menu::bar(ui, |ui| {
@@ -226,6 +419,177 @@ impl eframe::App for GUI {
                     }
                 });
 
+                let mut profiles = self.profiles.lock().unwrap();
+                egui::ComboBox::from_id_source("profile")
+                    .selected_text(
+                        profiles
+                            .active
+                            .clone()
+                            .unwrap_or_else(|| "No profile".to_owned()),
+                    )
+                    .show_ui(ui, |ui| {
+                        for profile in &profiles.profiles {
+                            let active = profiles.active.as_deref() == Some(&profile.name);
+                            if ui.selectable_label(active, &profile.name).clicked() && !active {
+                                profile_action =
+                                    Some(profiles::ProfileAction::Switch(profile.name.clone()));
+                            }
+                        }
+                    });
+
+                ui.menu_button("Profile", |ui| {
+                    ui.horizontal(|ui| {
+                        ui.add(
+                            TextEdit::singleline(&mut self.profile_name)
+                                .hint_text("New profile name")
+                                .desired_width(120.0),
+                        );
+                        let name = self.profile_name.trim().to_owned();
+                        if ui
+                            .add_enabled(
+                                !name.is_empty() && profiles.get(&name).is_none(),
+                                egui::Button::new("Create"),
+                            )
+                            .on_hover_text("Creates a profile out of the current settings.")
+                            .clicked()
+                        {
+                            profile_action = Some(profiles::ProfileAction::Create(name));
+                            self.profile_name.clear();
+                            ui.close_menu();
+                        }
+                    });
+
+                    ui.horizontal(|ui| {
+                        ui.add(
+                            TextEdit::singleline(&mut self.profile_import_text)
+                                .hint_text("Profile string or file path")
+                                .desired_width(120.0),
+                        );
+                        if ui
+                            .add_enabled(
+                                !self.profile_import_text.trim().is_empty(),
+                                egui::Button::new("Import"),
+                            )
+                            .clicked()
+                        {
+                            let imported = profiles::import(
+                                &self.profile_import_text,
+                                |champion_id| {
+                                    self.champion_search.name(champion_id).map(str::to_owned)
+                                },
+                                |key| {
+                                    self.summoner_spells
+                                        .iter()
+                                        .find(|spell| spell.key == key)
+                                        .map(|spell| spell.name.clone())
+                                },
+                            );
+                            match imported {
+                                Ok(mut imported) => {
+                                    // Imported profiles never replace an existing one
+                                    let name = imported.profile.name.clone();
+                                    let mut copy = 1;
+                                    while profiles.get(&imported.profile.name).is_some() {
+                                        copy += 1;
+                                        imported.profile.name = format!("{} ({})", name, copy);
+                                    }
+                                    self.profile_status = Some(imported.report());
+                                    profiles.profiles.push(imported.profile);
+                                    let _ = profiles.save(profiles::PROFILES_PATH);
+                                    self.profile_import_text.clear();
+                                }
+                                Err(error) => {
+                                    self.profile_status = Some(format!("Import failed: {}", error));
+                                }
+                            }
+                        }
+                    });
+                    if let Some(status) = &self.profile_status {
+                        ui.weak(status);
+                    }
+
+                    let active = profiles.active.clone();
+                    let Some(idx) = profiles
+                        .profiles
+                        .iter()
+                        .position(|profile| Some(&profile.name) == active.as_ref())
+                    else {
+                        return;
+                    };
+                    let mut changed = false;
+
+                    ui.separator();
+                    if ui.button("Save Current Settings").clicked() {
+                        profile_action = Some(profiles::ProfileAction::Save);
+                        ui.close_menu();
+                    }
+                    ui.horizontal(|ui| {
+                        let profile_string =
+                            profiles::export_string(&profiles.profiles[idx], |name| {
+                                self.summoner_spells
+                                    .iter()
+                                    .find(|spell| spell.name == name)
+                                    .map(|spell| spell.key)
+                            });
+                        if ui
+                            .button("Copy Profile String")
+                            .on_hover_text("Copies the profile to the clipboard so it can be shared.")
+                            .clicked()
+                        {
+                            ui.output_mut(|output| output.copied_text = profile_string.clone());
+                            self.profile_status = Some("Profile string copied.".to_owned());
+                        }
+                        if ui.button("Export to File").clicked() {
+                            self.profile_status = Some(
+                                match profiles::export_file(
+                                    &profiles.profiles[idx].name,
+                                    &profile_string,
+                                ) {
+                                    Ok(path) => format!("Exported to {}", path),
+                                    Err(error) => format!("Export failed: {}", error),
+                                },
+                            );
+                        }
+                    });
+                    ui.horizontal(|ui| {
+                        ui.label("Rune page:");
+                        changed |= ui
+                            .add(
+                                TextEdit::singleline(&mut profiles.profiles[idx].rune_page)
+                                    .hint_text("Keep current")
+                                    .desired_width(120.0),
+                            )
+                            .on_hover_text("Name of one of your rune pages, made current when switching to this profile.")
+                            .changed();
+                    });
+
+                    ui.label("Switch to this profile in lobbies for:");
+                    for (queue_id, name) in lobby::QUEUES {
+                        let mut used = profiles.profiles[idx].queue_ids.contains(&queue_id);
+                        if ui.checkbox(&mut used, name).changed() {
+                            // A queue belongs to a single profile
+                            for profile in profiles.profiles.iter_mut() {
+                                profile.queue_ids.retain(|id| *id != queue_id);
+                            }
+                            if used {
+                                profiles.profiles[idx].queue_ids.push(queue_id);
+                            }
+                            changed = true;
+                        }
+                    }
+
+                    ui.separator();
+                    if ui.button("Delete Profile").clicked() {
+                        profile_action = Some(profiles::ProfileAction::Delete);
+                        ui.close_menu();
+                    }
+
+                    if changed {
+                        let _ = profiles.save(profiles::PROFILES_PATH);
+                    }
+                });
+                drop(profiles);
+
                 if update_status.contains("outdated") {
                     if ui.button("Update").clicked() {
                         self.update_button_clicked = true;
@@ -274,7 +638,7 @@ impl eframe::App for GUI {
             .resizable(false)
             .exact_width(78.0)
             .show(ctx, |ui| {
-                let tabs = ["Settings", "Match State"];
+                let tabs = ["Settings", "Match State", "Lobby", "ARAM", "History"];
                 ui.with_layout(
                     egui::Layout::top_down_justified(egui::Align::Center),
                     |ui| {
@@ -299,7 +663,7 @@ impl eframe::App for GUI {
                     ui.horizontal(|ui| {
                         if ui.button("Clear Picks/Bans").clicked() {
                             champion_picks.clear();
-                            *ban_picks = None;
+                            ban_picks.clear();
                             self.clear_label_timer = Some(std::time::Instant::now());
                         }
                         if self.clear_label_timer.is_some() {
@@ -398,6 +762,44 @@ impl eframe::App for GUI {
                         ui.strong("Both summoner spells need to be selected");
                     }
 
+                    ui.horizontal(|ui| {
+                        let paused_label = if self.paused.load(Ordering::SeqCst) {
+                            "Pause All Automation: ON"
+                        } else {
+                            "Pause All Automation: OFF"
+                        };
+
+                        if ui
+                            .checkbox(&mut self.paused.load(Ordering::SeqCst), paused_label)
+                            .clicked()
+                        {
+                            let current_state = self.paused.load(Ordering::SeqCst);
+                            self.paused.store(!current_state, Ordering::SeqCst);
+                        }
+                    });
+
+                    ui.horizontal(|ui| {
+                        let minimize_to_tray_label =
+                            if self.minimize_to_tray.load(Ordering::SeqCst) {
+                                "Minimize to Tray: ON"
+                            } else {
+                                "Minimize to Tray: OFF"
+                            };
+
+                        if ui
+                            .checkbox(
+                                &mut self.minimize_to_tray.load(Ordering::SeqCst),
+                                minimize_to_tray_label,
+                            )
+                            .on_hover_text("Minimizing hides the window, click the tray icon to bring it back.")
+                            .clicked()
+                        {
+                            let current_state = self.minimize_to_tray.load(Ordering::SeqCst);
+                            self.minimize_to_tray
+                                .store(!current_state, Ordering::SeqCst);
+                        }
+                    });
+
                     ui.horizontal(|ui| {
                         let auto_accept_label = if self.auto_accept.load(Ordering::SeqCst) {
                             "Auto Accept: ON"
@@ -417,6 +819,41 @@ impl eframe::App for GUI {
                         }
                     });
 
+                    ui.horizontal(|ui| {
+                        let auto_requeue_label = if self.auto_requeue.load(Ordering::SeqCst) {
+                            "Auto Requeue: ON"
+                        } else {
+                            "Auto Requeue: OFF"
+                        };
+
+                        if ui
+                            .checkbox(
+                                &mut self.auto_requeue.load(Ordering::SeqCst),
+                                auto_requeue_label,
+                            )
+                            .clicked()
+                        {
+                            let current_state = self.auto_requeue.load(Ordering::SeqCst);
+                            self.auto_requeue.store(!current_state, Ordering::SeqCst);
+                        }
+                    });
+
+                    if self.auto_requeue.load(Ordering::SeqCst) {
+                        ui.horizontal(|ui| {
+                            ui.label("Delay (s):");
+                            ui.add(
+                                egui::DragValue::new(&mut *self.requeue_delay.lock().unwrap())
+                                    .clamp_range(0..=120),
+                            );
+                            ui.label("Max games:");
+                            ui.add(
+                                egui::DragValue::new(&mut *self.requeue_max_games.lock().unwrap())
+                                    .clamp_range(0..=50),
+                            )
+                            .on_hover_text("0 means no limit.");
+                        });
+                    }
+
                     // TODO:
                     // ui.horizontal(|ui| {
                     //     let rune_page_label = if self.rune_page_selection.load(Ordering::SeqCst) {
@@ -458,214 +895,130 @@ impl eframe::App for GUI {
                         }
                     });
 
+                    ui.horizontal(|ui| {
+                        let auto_trades_label = if self.auto_trades.load(Ordering::SeqCst) {
+                            "Auto-Respond to Trades: ON"
+                        } else {
+                            "Auto-Respond to Trades: OFF"
+                        };
+
+                        if ui
+                            .checkbox(
+                                &mut self.auto_trades.load(Ordering::SeqCst),
+                                auto_trades_label,
+                            )
+                            .on_hover_text(
+                                "Accepts trades for a champion higher on your pick list (or ARAM wishlist), declines the rest.",
+                            )
+                            .clicked()
+                        {
+                            let current_state = self.auto_trades.load(Ordering::SeqCst);
+                            self.auto_trades.store(!current_state, Ordering::SeqCst);
+                        }
+                    });
+
+                    ui.horizontal(|ui| {
+                        let auto_pick_order_swaps_label =
+                            if self.auto_pick_order_swaps.load(Ordering::SeqCst) {
+                                "Auto-Respond to Pick Order Swaps: ON"
+                            } else {
+                                "Auto-Respond to Pick Order Swaps: OFF"
+                            };
+
+                        if ui
+                            .checkbox(
+                                &mut self.auto_pick_order_swaps.load(Ordering::SeqCst),
+                                auto_pick_order_swaps_label,
+                            )
+                            .on_hover_text(
+                                "Accepts swaps that let you pick earlier, declines the rest.",
+                            )
+                            .clicked()
+                        {
+                            let current_state = self.auto_pick_order_swaps.load(Ordering::SeqCst);
+                            self.auto_pick_order_swaps
+                                .store(!current_state, Ordering::SeqCst);
+                        }
+                    });
+
                     ui.vertical(|ui| {
                         if pick_ban_selection {
                             if champion_picks.len() < 2 {
                                 ui.label("Enter champions to pick (2 max):");
-                                let text_edit_picks = ui.add(
-                                    TextEdit::singleline(&mut self.pick_text)
-                                        .hint_text("Press enter to skip."),
-                                );
-
-                                if !self.pick_text.is_empty() {
-                                    let pick_text_cleaned = self
-                                        .pick_text
-                                        .trim()
-                                        .replace(" ", "")
-                                        .as_str()
-                                        .replace("'", "")
-                                        .to_lowercase();
-
-                                    let matching_champions: Vec<String> = self
-                                        .champions
-                                        .iter()
-                                        .filter(|champion| {
-                                            champion
-                                                .name
-                                                .to_lowercase()
-                                                .starts_with(&pick_text_cleaned)
-                                        })
-                                        .map(|champion| champion.name.clone())
-                                        .collect();
-
-                                    if !matching_champions.is_empty() {
-                                        ui.push_id("pick suggestion", |ui| {
-                                            // this is done to ensure no id clash
-                                            eframe::egui::ComboBox::from_label("Name Suggestions")
-                                                .selected_text(matching_champions[0].clone())
-                                                .width(ui.available_width() / 3.0)
-                                                .show_ui(ui, |ui| {
-                                                    for suggestion in matching_champions {
-                                                        if ui
-                                                            .selectable_value(
-                                                                &mut self.pick_text,
-                                                                suggestion.clone(),
-                                                                suggestion,
-                                                            )
-                                                            .clicked()
-                                                        {
-                                                            text_edit_picks.request_focus();
-                                                        }
-                                                    }
-                                                });
-                                        });
-                                    }
-                                }
-
-                                if text_edit_picks.lost_focus()
-                                    && ui.input(|i| i.key_pressed(egui::Key::Enter))
-                                {
-                                    let pick_text_cleaned = self
-                                        .pick_text
-                                        .trim()
-                                        .replace(" ", "")
-                                        .as_str()
-                                        .replace("'", "")
-                                        .to_lowercase();
-
-                                    let matching_champion =
-                                        self.champions.iter().find(|champion| {
-                                            champion.name.to_lowercase() == pick_text_cleaned
-                                        });
-
-                                    if !pick_text_cleaned.is_empty() {
-                                        match matching_champion {
-                                            Some(champion) => {
-                                                if champion_picks
-                                                    .contains(&(champion.id, champion.name.clone()))
-                                                {
-                                                    self.text =
-                                                        "Champion has alread been selected."
-                                                            .to_string();
-                                                    self.pick_not_found_label_timer =
-                                                        Some(std::time::Instant::now());
-                                                } else {
-                                                    champion_picks
-                                                        .push((champion.id, champion.name.clone()));
-                                                }
-                                            }
-                                            None => {
-                                                self.text =
-                                                    "No champion found with the given name."
-                                                        .to_string();
-                                                self.pick_not_found_label_timer =
-                                                    Some(std::time::Instant::now());
-                                            }
+                                match champion_input(
+                                    ui,
+                                    "pick suggestion",
+                                    "Press enter to skip.",
+                                    &mut self.pick_text,
+                                    &self.champion_search,
+                                    &champion_availability,
+                                    |id| {
+                                        self.champion_icons
+                                            .get(ctx, id)
+                                            .unwrap_or(self.no_icon_img.texture_id(ctx))
+                                    },
+                                ) {
+                                    Some((_, Some(champion))) => {
+                                        if champion_picks.contains(&champion)
+                                            || ban_picks.contains(&champion)
+                                        {
+                                            self.text =
+                                                "Champion has alread been selected.".to_string();
+                                            self.pick_not_found_label_timer =
+                                                Some(std::time::Instant::now());
+                                        } else {
+                                            champion_picks.push(champion);
                                         }
-                                    } else {
-                                        champion_picks.push((0, "".to_string()));
                                     }
-                                    self.pick_text.clear();
-                                    text_edit_picks.request_focus();
+                                    Some((entered_text, None)) if !entered_text.is_empty() => {
+                                        self.text =
+                                            "No champion found with the given name.".to_string();
+                                        self.pick_not_found_label_timer =
+                                            Some(std::time::Instant::now());
+                                    }
+                                    Some(_) => champion_picks.push((0, "".to_string())),
+                                    None => {}
                                 }
                                 if self.pick_not_found_label_timer.is_some() {
                                     ui.weak(&self.text);
                                 }
                             }
 
-                            if ban_picks.is_none() {
-                                ui.label("Enter champion to ban:");
-                                let text_edit_bans = ui.add(
-                                    TextEdit::singleline(&mut self.ban_text)
-                                        .hint_text("Press enter to skip."),
-                                );
-
-                                if !self.ban_text.is_empty() {
-                                    let ban_text_cleaned = self
-                                        .ban_text
-                                        .trim()
-                                        .replace(" ", "")
-                                        .as_str()
-                                        .replace("'", "")
-                                        .to_lowercase();
-
-                                    let matching_champions: Vec<String> = self
-                                        .champions
-                                        .iter()
-                                        .filter(|champion| {
-                                            champion
-                                                .name
-                                                .to_lowercase()
-                                                .starts_with(&ban_text_cleaned)
-                                        })
-                                        .map(|champion| champion.name.clone())
-                                        .collect();
-
-                                    if !matching_champions.is_empty() {
-                                        eframe::egui::ComboBox::from_label("Name Suggestions")
-                                            .selected_text(matching_champions[0].clone())
-                                            .width(ui.available_width() / 3.0)
-                                            .show_ui(ui, |ui| {
-                                                for suggestion in matching_champions {
-                                                    if ui
-                                                        .selectable_value(
-                                                            &mut self.ban_text,
-                                                            suggestion.clone(),
-                                                            suggestion,
-                                                        )
-                                                        .clicked()
-                                                    {
-                                                        text_edit_bans.request_focus();
-                                                    }
-                                                }
-                                            });
-                                    }
-                                }
-
-                                if text_edit_bans.lost_focus()
-                                    && ui.input(|i| i.key_pressed(egui::Key::Enter))
-                                {
-                                    let ban_text_cleaned = self
-                                        .ban_text
-                                        .trim()
-                                        .replace(" ", "")
-                                        .as_str()
-                                        .replace("'", "")
-                                        .to_lowercase();
-
-                                    let matching_champion =
-                                        self.champions.iter().find(|champion| {
-                                            champion.name.to_lowercase() == ban_text_cleaned
-                                        });
-
-                                    if !ban_text_cleaned.is_empty() {
-                                        match matching_champion {
-                                            Some(champion) => {
-                                                if champion_picks
-                                                    .contains(&(champion.id, champion.name.clone()))
-                                                {
-                                                    self.text =
-                                                        "Champion has alread been selected."
-                                                            .to_string();
-                                                    self.ban_not_found_label_timer =
-                                                        Some(std::time::Instant::now());
-                                                } else {
-                                                    *ban_picks =
-                                                        Some((champion.id, champion.name.clone()));
-                                                }
-                                            }
-                                            None => {
-                                                self.text =
-                                                    "No champion found with the given name."
-                                                        .to_string();
-                                                self.ban_not_found_label_timer =
-                                                    Some(std::time::Instant::now());
-                                            }
+                            if ban_picks.len() < 2 {
+                                ui.label("Enter champions to ban (2 max):");
+                                match champion_input(
+                                    ui,
+                                    "ban suggestion",
+                                    "Press enter to skip.",
+                                    &mut self.ban_text,
+                                    &self.champion_search,
+                                    &HashMap::new(),
+                                    |id| {
+                                        self.champion_icons
+                                            .get(ctx, id)
+                                            .unwrap_or(self.no_icon_img.texture_id(ctx))
+                                    },
+                                ) {
+                                    Some((_, Some(champion))) => {
+                                        if champion_picks.contains(&champion)
+                                            || ban_picks.contains(&champion)
+                                        {
+                                            self.text =
+                                                "Champion has alread been selected.".to_string();
+                                            self.ban_not_found_label_timer =
+                                                Some(std::time::Instant::now());
+                                        } else {
+                                            ban_picks.push(champion);
                                         }
-                                    } else {
-                                        *ban_picks = Some((
-                                            0,
-                                            self.ban_text
-                                                .trim()
-                                                .replace(" ", "")
-                                                .as_str()
-                                                .replace("'", "")
-                                                .to_string()
-                                                .to_lowercase(),
-                                        ));
                                     }
-                                    self.ban_text.clear();
-                                    text_edit_bans.request_focus();
+                                    Some((entered_text, None)) if !entered_text.is_empty() => {
+                                        self.text =
+                                            "No champion found with the given name.".to_string();
+                                        self.ban_not_found_label_timer =
+                                            Some(std::time::Instant::now());
+                                    }
+                                    Some(_) => ban_picks.push((0, "".to_string())),
+                                    None => {}
                                 }
                                 if self.ban_not_found_label_timer.is_some() {
                                     ui.weak(&self.text);
@@ -674,38 +1027,246 @@ impl eframe::App for GUI {
                         }
                         if pick_ban_selection {
                             if champion_picks.len() == 2
-                                && champion_picks.get(0).unwrap().1.is_empty()
-                                && ban_picks.is_some()
-                                && ban_picks.as_ref().unwrap().1.is_empty()
-                                && champion_picks.get(1).unwrap().1.is_empty()
+                                && ban_picks.len() == 2
+                                && champion_picks
+                                    .iter()
+                                    .chain(ban_picks.iter())
+                                    .all(|(_, name)| name.is_empty())
                             {
                                 champion_picks.clear();
-                                *ban_picks = None;
+                                ban_picks.clear();
                                 self.pick_ban_selection.store(false, Ordering::SeqCst);
                             }
-                            if champion_picks.len() != 0 {
-                                ui.strong("Picks:");
-                                for (id, name) in &*champion_picks {
-                                    if !name.is_empty() {
-                                        ui.label(format!("ID:{id} Name:\"{name}\""));
+                            if !champion_picks.is_empty() || !ban_picks.is_empty() {
+                                let mut skin_choices = self.skin_choices.lock().unwrap();
+                                let champion_skins = self.champion_skins.lock().unwrap();
+                                champion_list_editor(
+                                    ui,
+                                    &mut champion_picks,
+                                    &mut ban_picks,
+                                    |id| {
+                                        self.champion_icons
+                                            .get(ctx, id)
+                                            .unwrap_or(self.no_icon_img.texture_id(ctx))
+                                    },
+                                    &champion_availability,
+                                    &mut skin_choices,
+                                    &champion_skins,
+                                );
+                            }
+
+                            ui.horizontal(|ui| {
+                                let respect_ally_hovers_label =
+                                    if self.respect_ally_hovers.load(Ordering::SeqCst) {
+                                        "Skip Bans Hovered by Allies: ON"
                                     } else {
-                                        ui.label("None");
-                                    }
+                                        "Skip Bans Hovered by Allies: OFF"
+                                    };
+
+                                if ui
+                                    .checkbox(
+                                        &mut self.respect_ally_hovers.load(Ordering::SeqCst),
+                                        respect_ally_hovers_label,
+                                    )
+                                    .clicked()
+                                {
+                                    let current_state =
+                                        self.respect_ally_hovers.load(Ordering::SeqCst);
+                                    self.respect_ally_hovers
+                                        .store(!current_state, Ordering::SeqCst);
+                                }
+                            });
+
+                            ui.horizontal(|ui| {
+                                let counter_pick_label =
+                                    if self.counter_pick.load(Ordering::SeqCst) {
+                                        "Counter-Pick: ON"
+                                    } else {
+                                        "Counter-Pick: OFF"
+                                    };
+
+                                if ui
+                                    .checkbox(
+                                        &mut self.counter_pick.load(Ordering::SeqCst),
+                                        counter_pick_label,
+                                    )
+                                    .on_hover_text(
+                                        "Picks the champion from your list that counters the enemy team the most, based on counters.json.",
+                                    )
+                                    .clicked()
+                                {
+                                    let current_state = self.counter_pick.load(Ordering::SeqCst);
+                                    self.counter_pick.store(!current_state, Ordering::SeqCst);
                                 }
+                            });
+                            if let Some(error) = self.counters_error.lock().unwrap().as_ref() {
+                                ui.weak(error);
                             }
-                            if ban_picks.is_some() {
-                                ui.strong("Ban:");
-                                if ban_picks.as_ref().unwrap().1.is_empty() {
-                                    ui.label("None");
+
+                            ui.horizontal(|ui| {
+                                let late_lock_label = if self.late_lock.load(Ordering::SeqCst) {
+                                    "Late Lock-In: ON"
                                 } else {
-                                    ui.label(format!(
-                                        "ID:{} Name:\"{}\"",
-                                        &ban_picks.as_ref().unwrap().0,
-                                        &ban_picks.as_ref().unwrap().1
-                                    ));
+                                    "Late Lock-In: OFF"
+                                };
+
+                                if ui
+                                    .checkbox(
+                                        &mut self.late_lock.load(Ordering::SeqCst),
+                                        late_lock_label,
+                                    )
+                                    .on_hover_text(
+                                        "Hovers the pick right away but only locks it when the timer is about to run out.",
+                                    )
+                                    .clicked()
+                                {
+                                    let current_state = self.late_lock.load(Ordering::SeqCst);
+                                    self.late_lock.store(!current_state, Ordering::SeqCst);
+                                }
+
+                                if self.late_lock.load(Ordering::SeqCst) {
+                                    ui.add(
+                                        egui::DragValue::new(
+                                            &mut *self.late_lock_seconds.lock().unwrap(),
+                                        )
+                                        .clamp_range(1..=25)
+                                        .suffix("s left"),
+                                    );
+                                }
+                            });
+                        }
+                    });
+
+                    ui.collapsing("Desktop Notifications", |ui| {
+                        for event in notifications::Event::ALL {
+                            if ui
+                                .checkbox(&mut self.notifier.is_enabled(event), event.label())
+                                .clicked()
+                            {
+                                self.notifier.toggle(event);
+                                let _ = self.notifier.save(notifications::NOTIFICATIONS_PATH);
+                            }
+                        }
+                    });
+
+                    ui.collapsing("Sound Alerts", |ui| {
+                        let mut settings = self.sounds.settings.lock().unwrap();
+                        let previous = settings.clone();
+                        egui::Grid::new("sounds").show(ui, |ui| {
+                            for cue in sounds::Cue::ALL {
+                                if ui
+                                    .checkbox(&mut settings.enabled.contains(&cue), cue.label())
+                                    .clicked()
+                                    && !settings.enabled.remove(&cue)
+                                {
+                                    settings.enabled.insert(cue);
                                 }
+                                ui.add(
+                                    egui::TextEdit::singleline(
+                                        settings.files.entry(cue).or_default(),
+                                    )
+                                    .hint_text("Bundled sound"),
+                                )
+                                .on_hover_text("Path to a WAV or OGG file");
+                                if ui.button("Test").clicked() {
+                                    self.sounds.preview(cue);
+                                }
+                                ui.end_row();
                             }
+                        });
+                        ui.add(egui::Slider::new(&mut settings.volume, 0.0..=1.0).text("Volume"));
+                        if *settings != previous {
+                            let _ = settings.save(sounds::SOUNDS_PATH);
+                        }
+                        if let Some(error) = self.sounds.error.lock().unwrap().as_ref() {
+                            ui.weak(error);
+                        }
+                    });
+
+                    ui.collapsing("Webhooks", |ui| {
+                        ui.horizontal(|ui| {
+                            ui.label("URL");
+                            ui.add(
+                                TextEdit::singleline(&mut self.webhook_text.url)
+                                    .hint_text("Discord or other webhook URL")
+                                    .desired_width(300.0),
+                            );
+                        });
+                        for event in webhooks::WebhookEvent::ALL {
+                            ui.horizontal(|ui| {
+                                if ui
+                                    .checkbox(
+                                        &mut self.webhook_text.enabled.contains(&event),
+                                        event.label(),
+                                    )
+                                    .clicked()
+                                    && !self.webhook_text.enabled.remove(&event)
+                                {
+                                    self.webhook_text.enabled.insert(event);
+                                }
+                                if ui.button("Send Test").clicked() {
+                                    self.webhooks.send_test(&self.webhook_text, event);
+                                }
+                            });
+                            let placeholders: Vec<String> = std::iter::once("event")
+                                .chain(event.placeholders().iter().copied())
+                                .map(|name| format!("{{{}}}", name))
+                                .collect();
+                            ui.add(
+                                TextEdit::multiline(
+                                    self.webhook_text.templates.entry(event).or_default(),
+                                )
+                                .hint_text(event.default_template())
+                                .desired_rows(2)
+                                .code_editor(),
+                            )
+                            .on_hover_text(format!(
+                                "JSON payload, leave empty for the default. Placeholders: {}",
+                                placeholders.join(", ")
+                            ));
                         }
+
+                        ui.horizontal(|ui| {
+                            if ui.button("Save Webhooks").clicked() {
+                                *self.webhooks.settings.lock().unwrap() = self.webhook_text.clone();
+                                let _ = self.webhook_text.save(webhooks::WEBHOOKS_PATH);
+                            }
+                            if let Some(status) = self.webhooks.status.lock().unwrap().as_ref() {
+                                ui.weak(status);
+                            }
+                        });
+                    });
+
+                    ui.collapsing("Hotkeys", |ui| {
+                        egui::Grid::new("hotkeys").show(ui, |ui| {
+                            for (label, binding) in [
+                                ("Toggle Auto Accept", &mut self.hotkey_text.toggle_auto_accept),
+                                ("Toggle Auto-Pick/Ban", &mut self.hotkey_text.toggle_pick_ban),
+                                ("Pause All Automation", &mut self.hotkey_text.pause_all),
+                            ] {
+                                ui.label(label);
+                                ui.add(
+                                    TextEdit::singleline(binding)
+                                        .hint_text("Disabled")
+                                        .desired_width(100.0),
+                                );
+                                ui.end_row();
+                            }
+                        });
+
+                        ui.horizontal(|ui| {
+                            if ui
+                                .button("Apply Hotkeys")
+                                .on_hover_text("e.g. Ctrl+Alt+A, Shift+F5. Leave empty to disable.")
+                                .clicked()
+                            {
+                                *self.hotkeys.lock().unwrap() = self.hotkey_text.clone();
+                                let _ = self.hotkey_text.save(hotkeys::HOTKEYS_PATH);
+                            }
+                            if let Some(status) = self.hotkey_status.lock().unwrap().as_ref() {
+                                ui.weak(status);
+                            }
+                        });
                     });
                 }
                 1 => {
@@ -713,30 +1274,450 @@ impl eframe::App for GUI {
                     if let Some(assigned_role) = self.assigned_role.lock().unwrap().clone() {
                         ui.label(format!("Role: {}", assigned_role));
                     }
+                    let champ_select = self.champ_select.lock().unwrap().clone();
+                    if let Some((draft, fetched_at)) = champ_select {
+                        ui.separator();
+                        champ_select_view(
+                            ui,
+                            &draft,
+                            fetched_at.elapsed(),
+                            &self.champion_search,
+                            |id| {
+                                self.champion_icons
+                                    .get(ctx, id)
+                                    .unwrap_or(self.no_icon_img.texture_id(ctx))
+                            },
+                            |key| {
+                                let name = self
+                                    .summoner_spells
+                                    .iter()
+                                    .find(|spell| spell.key == key)
+                                    .map(|spell| spell.name.clone())
+                                    .unwrap_or_default();
+                                let texture_id = self
+                                    .images
+                                    .get(&name)
+                                    .unwrap_or(&self.no_icon_img)
+                                    .texture_id(ctx);
+                                (texture_id, name)
+                            },
+                        );
+                    } else if let Some(champion_id) = *self.current_champion.lock().unwrap() {
+                        ui.horizontal(|ui| {
+                            ui.image(
+                                self.champion_icons
+                                    .get(ctx, champion_id)
+                                    .unwrap_or(self.no_icon_img.texture_id(ctx)),
+                                vec2(32.0, 32.0),
+                            );
+                            ui.label(format!(
+                                "Champion: {}",
+                                self.champion_search.name(champion_id).unwrap_or_default()
+                            ));
+                        });
+                    }
                 }
-                2 => {}
-                _ => unreachable!(),
-            }
+                2 => {
+                    let mut lobby_settings = self.lobby_settings.lock().unwrap();
+                    let mut lobby_action = self.lobby_action.lock().unwrap();
+
+                    egui::ComboBox::from_label("Queue")
+                        .selected_text(lobby::queue_name(lobby_settings.queue_id))
+                        .show_ui(ui, |ui| {
+                            for (queue_id, name) in lobby::QUEUES {
+                                ui.selectable_value(&mut lobby_settings.queue_id, queue_id, name);
+                            }
+                        });
+
+                    if lobby_settings.uses_positions() {
+                        egui::ComboBox::from_label("Primary Role")
+                            .selected_text(lobby::position_name(&lobby_settings.first_position))
+                            .show_ui(ui, |ui| {
+                                for (position, name) in lobby::POSITIONS {
+                                    ui.selectable_value(
+                                        &mut lobby_settings.first_position,
+                                        position.to_owned(),
+                                        name,
+                                    );
+                                }
+                            });
 
-            ui.vertical_centered_justified(|ui| {
-                ui.add_space(ui.available_size().y - ui.spacing().item_spacing.y * 11.0);
-                ui.weak(update_status);
-                if let Some(status) = connection_status.clone() {
-                    ui.weak(status.clone());
-                }
-            });
-        });
+                        if lobby_settings.first_position != "FILL" {
+                            egui::ComboBox::from_label("Secondary Role")
+                                .selected_text(lobby::position_name(
+                                    &lobby_settings.second_position,
+                                ))
+                                .show_ui(ui, |ui| {
+                                    for (position, name) in lobby::POSITIONS {
+                                        if position == lobby_settings.first_position {
+                                            continue;
+                                        }
+                                        ui.selectable_value(
+                                            &mut lobby_settings.second_position,
+                                            position.to_owned(),
+                                            name,
+                                        );
+                                    }
+                                });
 
-        ctx.request_repaint_after(tokio::time::Duration::from_millis(500));
-    }
+                            if lobby_settings.first_position == lobby_settings.second_position {
+                                ui.strong("Primary and secondary roles need to be different");
+                            }
+                        }
+                    }
 
-    fn on_exit(&mut self, _gl: Option<&eframe::glow::Context>) {
-        std::process::exit(0);
-    }
-}
+                    ui.checkbox(
+                        &mut lobby_settings.start_search,
+                        "Start queue after creating the lobby",
+                    );
 
-async fn update_checker(update_status: Arc<Mutex<String>>) -> Result<String, Box<dyn Error>> {
-    let repo_owner = "tacticaldeuce";
+                    let roles_valid = lobby_settings.roles_valid();
+                    ui.horizontal(|ui| {
+                        if ui
+                            .add_enabled(roles_valid, egui::Button::new("Create Lobby"))
+                            .clicked()
+                        {
+                            *lobby_action = Some(lobby::LobbyAction::Create);
+                        }
+                        if lobby_settings.uses_positions()
+                            && ui
+                                .add_enabled(roles_valid, egui::Button::new("Set Roles"))
+                                .clicked()
+                        {
+                            *lobby_action = Some(lobby::LobbyAction::SetPositions);
+                        }
+                        if lobby_action.is_some() {
+                            ui.spinner();
+                        }
+                    });
+                }
+                3 => {
+                    let mut aram_wishlist = self.aram_wishlist.lock().unwrap();
+
+                    ui.horizontal(|ui| {
+                        let aram_bench_swap_label = if self.aram_bench_swap.load(Ordering::SeqCst)
+                        {
+                            "ARAM Bench Swap: ON"
+                        } else {
+                            "ARAM Bench Swap: OFF"
+                        };
+
+                        if ui
+                            .checkbox(
+                                &mut self.aram_bench_swap.load(Ordering::SeqCst),
+                                aram_bench_swap_label,
+                            )
+                            .clicked()
+                        {
+                            let current_state = self.aram_bench_swap.load(Ordering::SeqCst);
+                            self.aram_bench_swap.store(!current_state, Ordering::SeqCst);
+                        }
+                    });
+
+                    ui.horizontal(|ui| {
+                        let aram_reroll_label = if self.aram_reroll.load(Ordering::SeqCst) {
+                            "Reroll When Nothing Is Available: ON"
+                        } else {
+                            "Reroll When Nothing Is Available: OFF"
+                        };
+
+                        if ui
+                            .checkbox(
+                                &mut self.aram_reroll.load(Ordering::SeqCst),
+                                aram_reroll_label,
+                            )
+                            .clicked()
+                        {
+                            let current_state = self.aram_reroll.load(Ordering::SeqCst);
+                            self.aram_reroll.store(!current_state, Ordering::SeqCst);
+                        }
+                    });
+
+                    ui.label("Enter champions to swap to (highest priority first):");
+                    match champion_input(
+                        ui,
+                        "aram suggestion",
+                        "",
+                        &mut self.aram_text,
+                        &self.champion_search,
+                        &champion_availability,
+                        |id| {
+                            self.champion_icons
+                                .get(ctx, id)
+                                .unwrap_or(self.no_icon_img.texture_id(ctx))
+                        },
+                    ) {
+                        Some((_, Some(entry))) => {
+                            if aram_wishlist.contains(&entry) {
+                                self.text = "Champion has alread been selected.".to_string();
+                                self.aram_not_found_label_timer = Some(std::time::Instant::now());
+                            } else {
+                                aram_wishlist.push(entry);
+                            }
+                        }
+                        Some((entered_text, None)) if !entered_text.is_empty() => {
+                            self.text = "No champion found with the given name.".to_string();
+                            self.aram_not_found_label_timer = Some(std::time::Instant::now());
+                        }
+                        _ => {}
+                    }
+                    if self.aram_not_found_label_timer.is_some() {
+                        ui.weak(&self.text);
+                    }
+
+                    if !aram_wishlist.is_empty() {
+                        ui.strong("Wishlist:");
+                        let mut removed = None;
+                        for (idx, (id, name)) in aram_wishlist.iter().enumerate() {
+                            ui.horizontal(|ui| {
+                                ui.label(format!("{}. ID:{id} Name:\"{name}\"", idx + 1));
+                                if ui.small_button("Remove").clicked() {
+                                    removed = Some(idx);
+                                }
+                            });
+                        }
+                        if let Some(idx) = removed {
+                            aram_wishlist.remove(idx);
+                        }
+                    }
+                }
+                4 => {
+                    ui.horizontal(|ui| {
+                        let record_drafts_label = if self.record_drafts.load(Ordering::SeqCst) {
+                            "Record Drafts: ON"
+                        } else {
+                            "Record Drafts: OFF"
+                        };
+
+                        if ui
+                            .checkbox(
+                                &mut self.record_drafts.load(Ordering::SeqCst),
+                                record_drafts_label,
+                            )
+                            .on_hover_text(
+                                "Saves every change of the champion select to utils/drafts, one file per game.",
+                            )
+                            .clicked()
+                        {
+                            let current_state = self.record_drafts.load(Ordering::SeqCst);
+                            self.record_drafts.store(!current_state, Ordering::SeqCst);
+                        }
+
+                        if ui.button("Export Drafts to CSV").clicked() {
+                            let champion_search = &self.champion_search;
+                            self.export_status = Some(
+                                match recorder::export_csv(recorder::DRAFTS_DIR, |champion_id| {
+                                    champion_search
+                                        .name(champion_id)
+                                        .unwrap_or("Unknown")
+                                        .to_owned()
+                                }) {
+                                    Ok(drafts) => format!(
+                                        "Exported {} drafts to {}.",
+                                        drafts,
+                                        recorder::DRAFTS_DIR
+                                    ),
+                                    Err(error) => format!("Export failed: {}", error),
+                                },
+                            );
+                        }
+                    });
+                    if let Some(export_status) = &self.export_status {
+                        ui.weak(export_status);
+                    }
+
+                    let match_history = self.match_history.lock().unwrap();
+                    let champion_name = |champion_id: u32| {
+                        self.champion_search
+                            .name(champion_id)
+                            .unwrap_or("Unknown")
+                            .to_owned()
+                    };
+                    let position_name = |position: &str| {
+                        if position.is_empty() {
+                            "No Role".to_owned()
+                        } else {
+                            lobby::position_name(&position.to_uppercase()).to_owned()
+                        }
+                    };
+                    let spell_name = |key: u32| {
+                        self.summoner_spells
+                            .iter()
+                            .find(|spell| spell.key == key)
+                            .map(|spell| spell.name.clone())
+                            .unwrap_or_default()
+                    };
+
+                    let mut champions: Vec<u32> = match_history
+                        .iter()
+                        .map(|record| record.champion_id)
+                        .collect();
+                    champions.sort_by_key(|champion_id| champion_name(*champion_id));
+                    champions.dedup();
+                    let mut positions: Vec<String> = match_history
+                        .iter()
+                        .map(|record| record.position.clone())
+                        .collect();
+                    positions.sort();
+                    positions.dedup();
+                    let mut queues: Vec<u32> = match_history
+                        .iter()
+                        .map(|record| record.queue_id)
+                        .collect();
+                    queues.sort();
+                    queues.dedup();
+
+                    let filter = &mut self.history_filter;
+                    ui.horizontal_wrapped(|ui| {
+                        egui::ComboBox::from_label("Champion")
+                            .selected_text(
+                                filter
+                                    .champion_id
+                                    .map(champion_name)
+                                    .unwrap_or("All".to_owned()),
+                            )
+                            .show_ui(ui, |ui| {
+                                ui.selectable_value(&mut filter.champion_id, None, "All");
+                                for champion_id in champions {
+                                    ui.selectable_value(
+                                        &mut filter.champion_id,
+                                        Some(champion_id),
+                                        champion_name(champion_id),
+                                    );
+                                }
+                            });
+                        egui::ComboBox::from_label("Role")
+                            .selected_text(
+                                filter
+                                    .position
+                                    .as_deref()
+                                    .map(position_name)
+                                    .unwrap_or("All".to_owned()),
+                            )
+                            .show_ui(ui, |ui| {
+                                ui.selectable_value(&mut filter.position, None, "All");
+                                for position in positions {
+                                    let name = position_name(&position);
+                                    ui.selectable_value(&mut filter.position, Some(position), name);
+                                }
+                            });
+                        egui::ComboBox::from_label("Queue")
+                            .selected_text(
+                                filter
+                                    .queue_id
+                                    .map(lobby::queue_name)
+                                    .unwrap_or("All".to_owned()),
+                            )
+                            .show_ui(ui, |ui| {
+                                ui.selectable_value(&mut filter.queue_id, None, "All");
+                                for queue_id in queues {
+                                    ui.selectable_value(
+                                        &mut filter.queue_id,
+                                        Some(queue_id),
+                                        lobby::queue_name(queue_id),
+                                    );
+                                }
+                            });
+                    });
+
+                    let games: Vec<&history::GameRecord> = match_history
+                        .iter()
+                        .filter(|record| filter.matches(record))
+                        .collect();
+                    if games.is_empty() {
+                        ui.weak("No games recorded yet.");
+                    } else {
+                        egui::ScrollArea::vertical()
+                            .max_height(ui.available_height() - 40.0)
+                            .show(ui, |ui| {
+                                let win_rate_grid =
+                                    |ui: &mut egui::Ui, id: &str, rows: Vec<(String, u32, u32)>| {
+                                        egui::Grid::new(id).striped(true).show(ui, |ui| {
+                                            for (name, games, wins) in rows {
+                                                ui.label(name);
+                                                ui.label(format!("{}W {}L", wins, games - wins));
+                                                ui.label(format!(
+                                                    "{:.0}%",
+                                                    100.0 * wins as f32 / games as f32
+                                                ));
+                                                ui.end_row();
+                                            }
+                                        });
+                                    };
+
+                                ui.strong("Win Rate per Champion");
+                                win_rate_grid(
+                                    ui,
+                                    "champion win rates",
+                                    history::win_rates(games.iter().copied(), |record| {
+                                        champion_name(record.champion_id)
+                                    }),
+                                );
+                                ui.strong("Win Rate per Role");
+                                win_rate_grid(
+                                    ui,
+                                    "role win rates",
+                                    history::win_rates(games.iter().copied(), |record| {
+                                        position_name(&record.position)
+                                    }),
+                                );
+
+                                ui.strong(format!("Games ({})", games.len()));
+                                egui::Grid::new("games").striped(true).show(ui, |ui| {
+                                    for record in games.iter().rev() {
+                                        ui.label(champion_name(record.champion_id));
+                                        ui.label(position_name(&record.position));
+                                        ui.label(lobby::queue_name(record.queue_id));
+                                        ui.label(format!(
+                                            "{}/{}",
+                                            spell_name(record.spell1_id),
+                                            spell_name(record.spell2_id)
+                                        ));
+                                        ui.label(match record.win {
+                                            Some(true) => "Win",
+                                            Some(false) => "Loss",
+                                            None => "Unknown",
+                                        });
+                                        if record.auto_picked {
+                                            ui.weak("Auto-picked");
+                                        }
+                                        ui.end_row();
+                                    }
+                                });
+                            });
+                    }
+                }
+                _ => unreachable!(),
+            }
+
+            ui.vertical_centered_justified(|ui| {
+                ui.add_space(ui.available_size().y - ui.spacing().item_spacing.y * 11.0);
+                ui.weak(update_status);
+                if let Some(status) = connection_status.clone() {
+                    ui.weak(status.clone());
+                }
+            });
+        });
+
+        drop(champion_picks);
+        drop(ban_picks);
+        drop(selected_image1);
+        drop(selected_image2);
+        if let Some(action) = profile_action {
+            self.handle_profile_action(action);
+        }
+
+        ctx.request_repaint_after(tokio::time::Duration::from_millis(500));
+    }
+
+    fn on_exit(&mut self, _gl: Option<&eframe::glow::Context>) {
+        tray::remove_icon();
+        std::process::exit(0);
+    }
+}
+
+async fn update_checker(update_status: Arc<Mutex<String>>) -> Result<String, Box<dyn Error>> {
+    let repo_owner = "tacticaldeuce";
     let repo_name = "circuit-watcher";
     let url = format!(
         "https://api.github.com/repos/{}/{}/releases/latest",
@@ -784,6 +1765,355 @@ fn hide_console_window() {
     }
 }
 
+/// Live view of a champion select: the timer, the actions in progress and both teams with their picks and bans.
+fn champ_select_view(
+    ui: &mut egui::Ui,
+    draft: &draft::Draft,
+    elapsed: std::time::Duration,
+    champion_search: &search::ChampionSearch,
+    mut champion_icon: impl FnMut(u32) -> egui::TextureId,
+    spell_icon: impl Fn(u32) -> (egui::TextureId, String),
+) {
+    let timer_phase = match draft.timer_phase.as_str() {
+        "PLANNING" => "Planning",
+        "BAN_PICK" => "Bans and Picks",
+        "FINALIZATION" => "Finalization",
+        "GAME_STARTING" => "Game Starting",
+        phase => phase,
+    };
+    let seconds_left = draft
+        .time_left_ms
+        .saturating_sub(elapsed.as_millis() as u64)
+        / 1000;
+    ui.horizontal(|ui| {
+        ui.strong(timer_phase);
+        ui.label(format!("{}s left", seconds_left));
+    });
+
+    if draft.is_local_turn() {
+        ui.colored_label(ui.visuals().warn_fg_color, "It's your turn!");
+    }
+    for action in &draft.current_actions {
+        if action.ally && action.actor_cell_id == draft.local_cell_id {
+            continue;
+        }
+        let team = if action.ally { "Ally" } else { "Enemy" };
+        ui.weak(format!("{} {} in progress", team, action.kind));
+    }
+
+    let champion_name = |champion_id: u32| -> String {
+        champion_search
+            .name(champion_id)
+            .unwrap_or("Unknown")
+            .to_owned()
+    };
+
+    ui.columns(2, |columns| {
+        let teams = [
+            ("Your Team", &draft.my_team, &draft.my_bans, true),
+            ("Enemy Team", &draft.their_team, &draft.their_bans, false),
+        ];
+        for (ui, (title, cells, bans, ours)) in columns.iter_mut().zip(teams) {
+            ui.strong(title);
+            ui.horizontal_wrapped(|ui| {
+                ui.label("Bans:");
+                if bans.is_empty() {
+                    ui.weak("None");
+                }
+                for champion_id in bans {
+                    ui.image(champion_icon(*champion_id), vec2(20.0, 20.0))
+                        .on_hover_text(champion_name(*champion_id));
+                }
+            });
+
+            for cell in cells {
+                let acting = draft
+                    .current_actions
+                    .iter()
+                    .any(|action| action.actor_cell_id == cell.cell_id);
+                let champion_id = if cell.champion_id != 0 {
+                    cell.champion_id
+                } else {
+                    cell.pick_intent
+                };
+
+                ui.horizontal(|ui| {
+                    ui.image(champion_icon(champion_id), vec2(28.0, 28.0));
+                    if cell.spell1_id != 0 || cell.spell2_id != 0 {
+                        ui.vertical(|ui| {
+                            for spell_id in [cell.spell1_id, cell.spell2_id] {
+                                let (texture_id, name) = spell_icon(spell_id);
+                                ui.image(texture_id, vec2(13.0, 13.0)).on_hover_text(name);
+                            }
+                        });
+                    }
+                    ui.vertical(|ui| {
+                        let status = if champion_id == 0 {
+                            ""
+                        } else if cell.locked {
+                            " (locked)"
+                        } else if cell.champion_id != 0 {
+                            " (hovering)"
+                        } else {
+                            " (intent)"
+                        };
+                        let name = if champion_id == 0 {
+                            "No champion".to_owned()
+                        } else {
+                            champion_name(champion_id)
+                        };
+                        if ours && cell.cell_id == draft.local_cell_id {
+                            ui.strong(format!("{}{} - You", name, status));
+                        } else {
+                            ui.label(format!("{}{}", name, status));
+                        }
+
+                        let mut details = Vec::new();
+                        if !cell.position.is_empty() {
+                            details.push(
+                                lobby::position_name(&cell.position.to_uppercase()).to_owned(),
+                            );
+                        }
+                        if acting {
+                            details.push("Choosing...".to_owned());
+                        }
+                        if !details.is_empty() {
+                            ui.weak(details.join(" - "));
+                        }
+                    });
+                });
+            }
+        }
+    });
+}
+
+/// Skin selection dropdown for a champion in the pick list, choices are stored in `skin_choices`.
+/// Only skins that are owned (and already fetched from the LeagueClient) can be chosen.
+fn skin_selector(
+    ui: &mut egui::Ui,
+    champion_id: u32,
+    skin_choices: &mut HashMap<u32, skins::SkinChoice>,
+    champion_skins: &HashMap<u32, Vec<skins::Skin>>,
+) {
+    let owned_skins = champion_skins
+        .get(&champion_id)
+        .map(|skins| skins::owned_skins(skins))
+        .unwrap_or_default();
+
+    let selected_text = match skin_choices.get(&champion_id) {
+        None => "Default Skin".to_owned(),
+        Some(skins::SkinChoice::Random) => "Random Skin".to_owned(),
+        Some(skins::SkinChoice::Skin(skin_id)) => owned_skins
+            .iter()
+            .find(|(id, _)| id == skin_id)
+            .map(|(_, name)| name.clone())
+            .unwrap_or_else(|| format!("Skin {}", skin_id)),
+    };
+
+    egui::ComboBox::from_id_source(("skin", champion_id))
+        .selected_text(selected_text)
+        .show_ui(ui, |ui| {
+            if ui
+                .selectable_label(!skin_choices.contains_key(&champion_id), "Default Skin")
+                .clicked()
+            {
+                skin_choices.remove(&champion_id);
+            }
+            let mut choice = skin_choices.get(&champion_id).copied();
+            ui.selectable_value(&mut choice, Some(skins::SkinChoice::Random), "Random Skin");
+            for (skin_id, name) in owned_skins {
+                ui.selectable_value(&mut choice, Some(skins::SkinChoice::Skin(skin_id)), name);
+            }
+            if let Some(choice) = choice {
+                skin_choices.insert(champion_id, choice);
+            }
+        });
+}
+
+/// Editor for the pick and ban lists. Entries can be removed, reordered by dragging their icon
+/// and dragged from one list to the other. Dropping an entry on a list header moves it to the end of that list,
+/// dropping it on an entry of a full list swaps the two.
+fn champion_list_editor(
+    ui: &mut egui::Ui,
+    champion_picks: &mut Vec<(u32, String)>,
+    ban_picks: &mut Vec<(u32, String)>,
+    mut champion_icon: impl FnMut(u32) -> egui::TextureId,
+    champion_availability: &HashMap<u32, ownership::Availability>,
+    skin_choices: &mut HashMap<u32, skins::SkinChoice>,
+    champion_skins: &HashMap<u32, Vec<skins::Skin>>,
+) {
+    let mut lists = [champion_picks, ban_picks];
+    // Positions are (list, index) pairs, index being the list length for the header
+    let mut dragged = None;
+    let mut dropped = false;
+    let mut hovered = None;
+    let mut removed = None;
+
+    for (list_idx, title) in ["Picks:", "Bans:"].into_iter().enumerate() {
+        let list = &lists[list_idx];
+        let header = ui
+            .horizontal(|ui| {
+                ui.strong(title);
+                if list.is_empty() {
+                    ui.weak("Drag a champion here.");
+                }
+            })
+            .response;
+        if ui.rect_contains_pointer(header.rect) {
+            hovered = Some(((list_idx, list.len()), header.rect));
+        }
+
+        for (idx, (id, name)) in list.iter().enumerate() {
+            let row = ui
+                .horizontal(|ui| {
+                    let handle = ui
+                        .add(
+                            egui::Image::new(champion_icon(*id), vec2(20.0, 20.0))
+                                .sense(egui::Sense::drag()),
+                        )
+                        .on_hover_cursor(egui::CursorIcon::Grab);
+                    if handle.dragged() || handle.drag_released() {
+                        dragged = Some((list_idx, idx));
+                        dropped = handle.drag_released();
+                    }
+
+                    if name.is_empty() {
+                        ui.label(format!("{}. None", idx + 1));
+                    } else if list_idx == 0 {
+                        ui.label(format!(
+                            "{}. {}",
+                            idx + 1,
+                            tagged_name(*id, name, champion_availability)
+                        ));
+                        skin_selector(ui, *id, skin_choices, champion_skins);
+                    } else {
+                        ui.label(format!("{}. {}", idx + 1, name));
+                    }
+
+                    if ui.small_button("Remove").clicked() {
+                        removed = Some((list_idx, idx));
+                    }
+                })
+                .response;
+            if ui.rect_contains_pointer(row.rect) {
+                hovered = Some(((list_idx, idx), row.rect));
+            }
+        }
+    }
+
+    if let Some((list_idx, idx)) = removed {
+        lists[list_idx].remove(idx);
+        return;
+    }
+
+    let Some((from_list, from_idx)) = dragged else {
+        return;
+    };
+    if !dropped {
+        let name = match lists[from_list][from_idx].1.as_str() {
+            "" => "None",
+            name => name,
+        };
+        egui::show_tooltip_at_pointer(ui.ctx(), egui::Id::new("dragged champion"), |ui| {
+            ui.label(name);
+        });
+        if let Some((_, rect)) = hovered {
+            ui.painter()
+                .rect_stroke(rect, 2.0, ui.visuals().selection.stroke);
+        }
+        return;
+    }
+
+    let Some(((to_list, to_idx), _)) = hovered else {
+        return;
+    };
+    let entry = lists[from_list][from_idx].clone();
+    if from_list == to_list {
+        let list = &mut lists[from_list];
+        list.remove(from_idx);
+        list.insert(to_idx.min(list.len()), entry);
+    } else if !entry.1.is_empty() && lists[to_list].contains(&entry) {
+        // The champion is already in the other list
+    } else if lists[to_list].len() < 2 {
+        lists[from_list].remove(from_idx);
+        lists[to_list].insert(to_idx, entry);
+    } else if to_idx < lists[to_list].len() {
+        let swapped = std::mem::replace(&mut lists[to_list][to_idx], entry);
+        lists[from_list][from_idx] = swapped;
+    }
+}
+
+/// The champion name followed by its availability tag, untagged until the availability has been fetched.
+fn tagged_name(
+    champion_id: u32,
+    name: &str,
+    champion_availability: &HashMap<u32, ownership::Availability>,
+) -> String {
+    if champion_availability.is_empty() {
+        return name.to_owned();
+    }
+
+    format!(
+        "{}{}",
+        name,
+        ownership::Availability::of(champion_availability, champion_id).tag()
+    )
+}
+
+/// Text input with champion name suggestions, ranked by the champion search.
+///
+/// Returns the normalized entered text once enter is pressed, along with the champion it refers to if there is one.
+/// The text is cleared afterwards so another champion can be entered right away.
+fn champion_input(
+    ui: &mut egui::Ui,
+    id_source: &str,
+    hint: &str,
+    text: &mut String,
+    champion_search: &search::ChampionSearch,
+    champion_availability: &HashMap<u32, ownership::Availability>,
+    mut champion_icon: impl FnMut(u32) -> egui::TextureId,
+) -> Option<(String, Option<(u32, String)>)> {
+    let text_edit = ui.add(TextEdit::singleline(text).hint_text(hint));
+
+    let matching_champions: Vec<(u32, String, String)> = champion_search
+        .search(text)
+        .into_iter()
+        .map(|(id, name)| {
+            let label = tagged_name(id, &name, champion_availability);
+            (id, name, label)
+        })
+        .collect();
+
+    if !matching_champions.is_empty() {
+        ui.push_id(id_source, |ui| {
+            egui::ComboBox::from_label("Name Suggestions")
+                .selected_text(matching_champions[0].2.clone())
+                .width(ui.available_width() / 3.0)
+                .show_ui(ui, |ui| {
+                    for (id, suggestion, label) in matching_champions {
+                        ui.horizontal(|ui| {
+                            ui.image(champion_icon(id), vec2(16.0, 16.0));
+                            if ui.selectable_value(text, suggestion, label).clicked() {
+                                text_edit.request_focus();
+                            }
+                        });
+                    }
+                });
+        });
+    }
+
+    if text_edit.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter)) {
+        let entered_text = search::normalize(text);
+        let matching_champion = champion_search.find(text);
+
+        text.clear();
+        text_edit.request_focus();
+        return Some((entered_text, matching_champion));
+    }
+
+    None
+}
+
 fn image_loader(img_name: &str, img_bytes: &[u8]) -> (String, RetainedImage) {
     (
         img_name.to_string(),
@@ -794,22 +2124,87 @@ fn image_loader(img_name: &str, img_bytes: &[u8]) -> (String, RetainedImage) {
 #[tokio::main]
 async fn main() -> Result<(), Box<dyn Error>> {
     let options = eframe::NativeOptions {
-        // icon_data: None,
+        icon_data: egui_extras::image::load_image_bytes(include_bytes!("../utils/images/icon.png"))
+            .ok()
+            .map(|image| eframe::IconData {
+                rgba: image
+                    .pixels
+                    .iter()
+                    .flat_map(|pixel| pixel.to_srgba_unmultiplied())
+                    .collect(),
+                width: image.size[0] as u32,
+                height: image.size[1] as u32,
+            }),
         min_window_size: Some(vec2(330.0, 320.0)),
         initial_window_size: Some(egui::vec2(500.0, 400.0)),
         ..Default::default()
     };
 
+    // Debugging aids: `--record-lcu <file>` saves every LeagueClient request the worker makes,
+    // `--replay-lcu <file>` runs the worker against such a recording instead of the LeagueClient
+    let args: Vec<String> = std::env::args().collect();
+    let arg_value = |flag: &str| {
+        args.iter()
+            .position(|arg| arg == flag)
+            .and_then(|idx| args.get(idx + 1))
+            .cloned()
+    };
+    let lcu_recording = arg_value("--record-lcu")
+        .map(|path| {
+            lcu::Recording::create(&path).map_err(|error| {
+                format!(
+                    "Couldn't create the LeagueClient recording {}: {}",
+                    path, error
+                )
+            })
+        })
+        .transpose()?;
+    let lcu_replay = arg_value("--replay-lcu");
+    let lcu_replay_client = lcu_replay
+        .as_ref()
+        .map(|path| {
+            lcu::LcuClient::replay(path).map_err(|error| {
+                format!(
+                    "Couldn't read the LeagueClient recording {}: {}",
+                    path, error
+                )
+            })
+        })
+        .transpose()?;
+
     let app = GUI::new();
 
     let champion_picks_clone = Arc::clone(&app.champion_picks);
+    let icon_downloader = icons::IconDownloader::new(Arc::clone(&app.champion_icons.requested));
+    let current_champion_clone = Arc::clone(&app.current_champion);
+    let champ_select_clone = Arc::clone(&app.champ_select);
+    let match_history_clone = Arc::clone(&app.match_history);
+    let record_drafts_clone = Arc::clone(&app.record_drafts);
     let ban_picks_clone = Arc::clone(&app.ban_picks);
+    let respect_ally_hovers_clone = Arc::clone(&app.respect_ally_hovers);
+    let counter_pick_clone = Arc::clone(&app.counter_pick);
+    let counters_error_clone = Arc::clone(&app.counters_error);
+    let late_lock_clone = Arc::clone(&app.late_lock);
+    let late_lock_seconds_clone = Arc::clone(&app.late_lock_seconds);
+    let aram_bench_swap_clone = Arc::clone(&app.aram_bench_swap);
+    let aram_reroll_clone = Arc::clone(&app.aram_reroll);
+    let aram_wishlist_clone = Arc::clone(&app.aram_wishlist);
+    let auto_trades_clone = Arc::clone(&app.auto_trades);
+    let auto_pick_order_swaps_clone = Arc::clone(&app.auto_pick_order_swaps);
+    let skin_choices_clone = Arc::clone(&app.skin_choices);
+    let champion_skins_clone = Arc::clone(&app.champion_skins);
+    let champion_availability_clone = Arc::clone(&app.champion_availability);
     let connection_status = Arc::clone(&app.connection_status);
     let connection_status_clone = Arc::clone(&app.connection_status);
     let gameflow_status = Arc::clone(&app.gameflow_status);
     let pick_ban_selection_clone = Arc::clone(&app.pick_ban_selection);
     let rune_page_change_clone = Arc::clone(&app.rune_page_selection);
     let auto_accept_clone = Arc::clone(&app.auto_accept);
+    let auto_requeue_clone = Arc::clone(&app.auto_requeue);
+    let requeue_delay_clone = Arc::clone(&app.requeue_delay);
+    let requeue_max_games_clone = Arc::clone(&app.requeue_max_games);
+    let lobby_settings_clone = Arc::clone(&app.lobby_settings);
+    let lobby_action_clone = Arc::clone(&app.lobby_action);
     let update_status_clone = Arc::clone(&app.update_status);
     let current_version_clone = Arc::clone(&app.current_version);
     let update_clone = Arc::clone(&app.update);
@@ -818,8 +2213,51 @@ async fn main() -> Result<(), Box<dyn Error>> {
     let selected_image2_clone = Arc::clone(&app.selected_image2);
     let spell_selection_clone = Arc::clone(&app.spell_selection);
     let assigned_role_clone = Arc::clone(&app.assigned_role);
+    let live_settings = app.live_settings();
+    let profiles_clone = Arc::clone(&app.profiles);
+    let rune_page_request_clone = Arc::clone(&app.rune_page_request);
+    let paused_clone = Arc::clone(&app.paused);
+
+    tray::run(tray::Tray {
+        auto_accept: Arc::clone(&app.auto_accept),
+        pick_ban_selection: Arc::clone(&app.pick_ban_selection),
+        paused: Arc::clone(&app.paused),
+        minimize_to_tray: Arc::clone(&app.minimize_to_tray),
+        main_window: Arc::clone(&app.main_window),
+        gameflow_status: Arc::clone(&app.gameflow_status),
+        notifications: Arc::clone(&app.notifier.queue),
+    });
+    let notifier = app.notifier.clone();
+    let notifier_clone = app.notifier.clone();
+    let sounds_clone = app.sounds.clone();
+    app.webhooks.run();
+    let webhooks_clone = app.webhooks.clone();
+    let update_status_check = Arc::clone(&app.update_status);
+
+    let auto_accept_hotkey = Arc::clone(&app.auto_accept);
+    let pick_ban_hotkey = Arc::clone(&app.pick_ban_selection);
+    let paused_hotkey = Arc::clone(&app.paused);
+    let hotkey_notifier = app.notifier.clone();
+    hotkeys::listen(
+        Arc::clone(&app.hotkeys),
+        Arc::clone(&app.hotkey_status),
+        move |action| {
+            let (setting, label) = match action {
+                hotkeys::HotkeyAction::ToggleAutoAccept => (&auto_accept_hotkey, "Auto Accept"),
+                hotkeys::HotkeyAction::TogglePickBan => (&pick_ban_hotkey, "Auto-Pick/Ban"),
+                hotkeys::HotkeyAction::PauseAll => (&paused_hotkey, "Pause All Automation"),
+            };
+            let enabled = !setting.fetch_xor(true, Ordering::SeqCst);
+            // Shown from the tray icon, the window may be minimized or in the background
+            hotkey_notifier.show(
+                tray::WINDOW_TITLE,
+                format!("{}: {}", label, if enabled { "ON" } else { "OFF" }),
+            );
+        },
+    );
 
     tokio::spawn(async move {
+        let mut connected = false;
         loop {
             hide_console_window();
             let update = update_clone.load(Ordering::SeqCst);
@@ -865,6 +2303,11 @@ async fn main() -> Result<(), Box<dyn Error>> {
                     }
                 }
             }
+            if let Some(path) = &lcu_replay {
+                *connection_status.lock().unwrap() =
+                    Some(format!("Replaying LeagueClient session from {}", path));
+                continue;
+            }
             match LeagueClientConnector::parse_raw_info() {
                 Ok(lockfile) => {
                     let mut status = connection_status.lock().unwrap();
@@ -872,10 +2315,18 @@ async fn main() -> Result<(), Box<dyn Error>> {
                         "Connected to LeagueClient on https://127.0.0.1:{}",
                         lockfile.port
                     ));
+                    connected = true;
                 }
                 Err(_) => {
                     let mut status = connection_status.lock().unwrap();
                     *status = Some("LeagueClient not found, may be closed.".to_owned());
+                    if connected {
+                        notifier.notify(
+                            notifications::Event::Disconnected,
+                            "The LeagueClient was closed or crashed.",
+                        );
+                        connected = false;
+                    }
                 }
             }
         }
@@ -886,6 +2337,12 @@ async fn main() -> Result<(), Box<dyn Error>> {
         let current_version_clone = Arc::clone(&current_version_clone);
 
         *current_version_clone.lock().unwrap() = update_checker(update_status_clone).await.unwrap();
+        if update_status_check.lock().unwrap().contains("outdated") {
+            notifier_clone.notify(
+                notifications::Event::UpdateAvailable,
+                "A new version of Circuit Watcher can be downloaded from the app.",
+            );
+        }
 
         // Both of this while loops are to ensure there is a viable connection to the League Client
         while status.is_none() {
@@ -906,26 +2363,60 @@ async fn main() -> Result<(), Box<dyn Error>> {
         }
         tokio::time::sleep(tokio::time::Duration::from_secs(10)).await;
 
-        let mut lc_info = LeagueClientConnector::parse_raw_info().unwrap();
-        let mut auth_header =
-            HeaderValue::from_str(format!("Basic {}", lc_info.b64_auth).as_str()).unwrap();
-        let cert =
-            reqwest::Certificate::from_pem(include_bytes!("../utils/riotgames.pem")).unwrap();
-        let mut headers = header::HeaderMap::new();
-
-        headers.insert(AUTHORIZATION, auth_header.clone());
-        let mut rest_client = ClientBuilder::new()
-            .add_root_certificate(cert.clone())
-            .default_headers(headers)
-            .build()
-            .unwrap();
+        let mut lcu = match lcu_replay_client {
+            Some(lcu) => lcu,
+            None => {
+                let lc_info = LeagueClientConnector::parse_raw_info().unwrap();
+                lcu::LcuClient::connect(lc_info.port, &lc_info.b64_auth, lcu_recording.clone())
+            }
+        };
 
         let spells_data =
             std::fs::read_to_string("./utils/summoner_spells.json").expect("Failed to read file");
         let summoner_spells: Vec<SummonerSpell> =
             serde_json::from_str(&spells_data).expect("Failed to parse JSON");
+        let counter_table = match counters::load_counters("./utils/counters.json") {
+            Ok(counter_table) => counter_table,
+            Err(error) => {
+                *counters_error_clone.lock().unwrap() = Some(error);
+                counters::CounterTable::new()
+            }
+        };
+        let champions_data =
+            std::fs::read_to_string("./utils/champions.json").expect("Failed to read file");
+        let champion_names: HashMap<u32, String> =
+            serde_json::from_str::<Vec<Champion>>(&champions_data)
+                .expect("Failed to parse JSON")
+                .into_iter()
+                .map(|champion| (champion.id, champion.name))
+                .collect();
 
         let mut locked_champ = false;
+        let mut hovered_champ: Option<u32> = None;
+        // Set once the player changes something the worker sent, so it stops fighting them until the next champ select
+        let mut pick_override = false;
+        let mut ban_override = false;
+        let mut spells_override = false;
+        let mut sent_spells: Option<(u32, u32)> = None;
+        let mut expected_aram_champ: Option<u32> = None;
+        let mut applied_skin: Option<(u32, skins::SkinChoice)> = None;
+        let mut last_phase: Option<String> = None;
+        let mut requeued_games: u32 = 0;
+        let mut requeued_this_game = false;
+        let mut accepted_ready_check = false;
+        let mut ban_failure_notified = false;
+        // Kind of the action of the local player in progress and whether their champion is locked, to play sounds on changes
+        let mut local_turn: Option<String> = None;
+        let mut local_locked = false;
+        let mut availability_outdated = true;
+        // Champions whose skins couldn't be fetched, tried again on the next phase change
+        let mut failed_skins: HashSet<u32> = HashSet::new();
+        // The game being played, filled in during champion select and recorded once it ends
+        let mut pending_game: Option<history::GameRecord> = None;
+        // When the pending game ended, its result is polled until the end of game stats are in
+        let mut game_ended: Option<std::time::Instant> = None;
+        let mut draft_recorder = recorder::DraftRecorder::default();
+        let mut lobby_queue: Option<u32> = None;
         loop {
             if connection_status_clone
                 .lock()
@@ -937,18 +2428,11 @@ async fn main() -> Result<(), Box<dyn Error>> {
             {
                 match LeagueClientConnector::parse_raw_info() {
                     Ok(riotlockfile) => {
-                        lc_info = riotlockfile;
-                        auth_header =
-                            HeaderValue::from_str(format!("Basic {}", lc_info.b64_auth).as_str())
-                                .unwrap();
-                        headers = header::HeaderMap::new();
-
-                        headers.insert(AUTHORIZATION, auth_header.clone());
-                        rest_client = ClientBuilder::new()
-                            .add_root_certificate(cert.clone())
-                            .default_headers(headers)
-                            .build()
-                            .unwrap();
+                        lcu = lcu::LcuClient::connect(
+                            riotlockfile.port,
+                            &riotlockfile.b64_auth,
+                            lcu_recording.clone(),
+                        );
 
                         tokio::time::sleep(tokio::time::Duration::from_secs(10)).await;
                     }
@@ -958,84 +2442,407 @@ async fn main() -> Result<(), Box<dyn Error>> {
                 }
                 tokio::time::sleep(tokio::time::Duration::from_millis(500)).await;
             }
+            if paused_clone.load(Ordering::SeqCst) {
+                *gameflow_status.lock().unwrap() = "Automation paused".to_owned();
+                tokio::time::sleep(tokio::time::Duration::from_secs(1)).await;
+                continue;
+            }
+            if lcu.replay_finished() {
+                *gameflow_status.lock().unwrap() = "Replay finished".to_owned();
+                tokio::time::sleep(tokio::time::Duration::from_secs(1)).await;
+                continue;
+            }
 
             let champion_picks = champion_picks_clone.lock().unwrap().clone();
             let ban_picks = ban_picks_clone.lock().unwrap().clone();
+            let respect_ally_hovers = respect_ally_hovers_clone.load(Ordering::SeqCst);
+            let counter_pick = counter_pick_clone.load(Ordering::SeqCst);
+            let late_lock = late_lock_clone.load(Ordering::SeqCst);
+            let late_lock_seconds = *late_lock_seconds_clone.lock().unwrap();
+            let aram_bench_swap = aram_bench_swap_clone.load(Ordering::SeqCst);
+            let aram_reroll = aram_reroll_clone.load(Ordering::SeqCst);
+            let aram_wishlist = aram_wishlist_clone.lock().unwrap().clone();
+            let auto_trades = auto_trades_clone.load(Ordering::SeqCst);
+            let auto_pick_order_swaps = auto_pick_order_swaps_clone.load(Ordering::SeqCst);
             let gameflow_status_clone = Arc::clone(&gameflow_status);
             let pick_ban_selection = pick_ban_selection_clone.load(Ordering::SeqCst);
             let rune_change = rune_page_change_clone.load(Ordering::SeqCst);
             let auto_accept = auto_accept_clone.load(Ordering::SeqCst);
+            let auto_requeue = auto_requeue_clone.load(Ordering::SeqCst);
+            let requeue_delay = *requeue_delay_clone.lock().unwrap();
+            let requeue_max_games = *requeue_max_games_clone.lock().unwrap();
             let spell1 = Arc::clone(&selected_image1_clone);
             let spell2 = Arc::clone(&selected_image2_clone);
             let spell_selection = spell_selection_clone.load(Ordering::SeqCst);
             let assigned_position = Arc::clone(&assigned_role_clone);
 
-            let gameflow: serde_json::Value = rest_client
-                .get(format!(
-                    "https://127.0.0.1:{}/lol-gameflow/v1/session",
-                    lc_info.port
-                ))
-                .send()
-                .await
-                .unwrap()
-                .json()
+            let gameflow: serde_json::Value = lcu
+                .get("/lol-gameflow/v1/session")
                 .await
-                .unwrap();
+                .unwrap_or_default();
             let phase = gameflow["phase"].as_str();
+            let phase_changed = last_phase.as_deref() != phase;
+            last_phase = phase.map(|phase| phase.to_owned());
+
+            if !auto_requeue {
+                requeued_games = 0;
+            }
+
+            // Ownership only changes on purchases and the weekly free rotation, so phase changes are often enough.
+            // A failed fetch is tried again on every loop until it succeeds
+            if phase_changed {
+                availability_outdated = true;
+            }
+            if availability_outdated {
+                if let Ok(availability) = ownership::fetch_availability(&lcu).await {
+                    *champion_availability_clone.lock().unwrap() = availability;
+                    availability_outdated = false;
+                }
+            }
+            let champion_availability = champion_availability_clone.lock().unwrap().clone();
+
+            // Skins are fetched ahead of time so they can be chosen from the pick list
+            if phase_changed {
+                failed_skins.clear();
+            }
+            if phase != Some("InProgress") {
+                for (champion_id, name) in &champion_picks {
+                    let cached = champion_skins_clone
+                        .lock()
+                        .unwrap()
+                        .contains_key(champion_id);
+                    if name.is_empty() || cached || failed_skins.contains(champion_id) {
+                        continue;
+                    }
+                    match skins::fetch_skins(&lcu, *champion_id).await {
+                        Ok(skins) => {
+                            champion_skins_clone
+                                .lock()
+                                .unwrap()
+                                .insert(*champion_id, skins);
+                        }
+                        Err(_) => {
+                            failed_skins.insert(*champion_id);
+                        }
+                    }
+                }
+            }
+
+            if phase_changed && phase != Some("ChampSelect") {
+                *champ_select_clone.lock().unwrap() = None;
+                draft_recorder.finish();
+            }
+
+            if phase == Some("InProgress") {
+                if let Some(game) = pending_game.as_mut() {
+                    game.game_id = gameflow["gameData"]["gameId"].as_u64().unwrap_or(0);
+                    game.queue_id =
+                        gameflow["gameData"]["queue"]["id"].as_u64().unwrap_or(0) as u32;
+                }
+            }
+            // Games end in the post game screen, or when it's skipped (e.g. by leaving it)
+            let game_over = phase == Some("EndOfGame")
+                || (phase_changed
+                    && !matches!(
+                        phase,
+                        Some("InProgress")
+                            | Some("Reconnect")
+                            | Some("PreEndOfGame")
+                            | Some("WaitingForStats")
+                    ));
+            if game_over
+                && game_ended.is_none()
+                && matches!(&pending_game, Some(game) if game.game_id != 0)
+            {
+                game_ended = Some(std::time::Instant::now());
+            }
+            // The stats can come in a while after the game left, the game is recorded without a result
+            // if they don't come in time or another champion select starts
+            let finished = match (game_ended, &pending_game) {
+                (Some(ended), Some(game)) => {
+                    let win = history::fetch_result(&lcu, game.game_id)
+                        .await
+                        .unwrap_or(None);
+                    let give_up =
+                        ended.elapsed() >= history::RESULT_TIMEOUT || phase == Some("ChampSelect");
+                    (win.is_some() || give_up).then_some(win)
+                }
+                _ => None,
+            };
+            if let Some(win) = finished {
+                game_ended = None;
+                let mut game = pending_game.take().unwrap();
+                game.win = win;
+                game.timestamp = std::time::SystemTime::now()
+                    .duration_since(std::time::UNIX_EPOCH)
+                    .map(|duration| duration.as_secs())
+                    .unwrap_or(0);
+
+                let _ = history::append_record(history::HISTORY_PATH, &game);
+                let result = match game.win {
+                    Some(true) => "Victory",
+                    Some(false) => "Defeat",
+                    None => "Unknown result",
+                };
+                webhooks_clone.send(
+                    webhooks::WebhookEvent::GameEnded,
+                    &[
+                        ("result", result.to_owned()),
+                        (
+                            "champion",
+                            champion_names
+                                .get(&game.champion_id)
+                                .cloned()
+                                .unwrap_or_else(|| game.champion_id.to_string()),
+                        ),
+                        ("champion_id", game.champion_id.to_string()),
+                        ("game_id", game.game_id.to_string()),
+                        ("queue_id", game.queue_id.to_string()),
+                    ],
+                );
+                match_history_clone.lock().unwrap().push(game);
+            }
+
+            // Icons are only downloaded once, the GUI loads them from the cache afterwards
+            if phase_changed {
+                icon_downloader.clear_failed();
+            }
+            icon_downloader.start(&lcu);
+
+            let lobby_action = lobby_action_clone.lock().unwrap().take();
+            if let Some(action) = lobby_action {
+                let lobby_settings = lobby_settings_clone.lock().unwrap().clone();
+
+                match phase {
+                    Some("None") | Some("Lobby") | Some("EndOfGame") | None => {
+                        let mut lobby_ready = true;
+
+                        if action == lobby::LobbyAction::Create {
+                            lobby_ready = lobby::create_lobby(&lcu, lobby_settings.queue_id)
+                                .await
+                                .is_ok();
+                        }
+                        if lobby_ready && lobby_settings.uses_positions() {
+                            lobby_ready = lobby::set_position_preferences(
+                                &lcu,
+                                &lobby_settings.first_position,
+                                &lobby_settings.second_position,
+                            )
+                            .await
+                            .is_ok();
+                        }
+                        if lobby_ready
+                            && action == lobby::LobbyAction::Create
+                            && lobby_settings.start_search
+                        {
+                            lobby_ready = lobby::start_matchmaking(&lcu).await.is_ok();
+                        }
+
+                        if !lobby_ready {
+                            *gameflow_status_clone.lock().unwrap() =
+                                "Couldn't set up the lobby".to_owned();
+                            tokio::time::sleep(tokio::time::Duration::from_secs(2)).await;
+                        }
+                        continue;
+                    }
+                    _ => {
+                        *gameflow_status_clone.lock().unwrap() =
+                            "Can't change the lobby right now".to_owned();
+                        tokio::time::sleep(tokio::time::Duration::from_secs(2)).await;
+                    }
+                }
+            }
+
+            // Entering a lobby switches to the profile of its queue, only once so a profile picked by hand afterwards stays
+            let queue_id = gameflow["gameData"]["queue"]["id"]
+                .as_u64()
+                .map(|queue_id| queue_id as u32);
+            if matches!(phase, Some("None") | None) {
+                lobby_queue = None;
+            }
+            if phase == Some("Lobby") && queue_id != lobby_queue {
+                lobby_queue = queue_id;
+                let profile = queue_id.and_then(|queue_id| {
+                    profiles_clone.lock().unwrap().for_queue(queue_id).cloned()
+                });
+                let active = profiles_clone.lock().unwrap().active.clone();
+                if let Some(profile) =
+                    profile.filter(|profile| Some(&profile.name) != active.as_ref())
+                {
+                    live_settings.apply(&profile);
+                    {
+                        let mut profiles = profiles_clone.lock().unwrap();
+                        profiles.active = Some(profile.name.clone());
+                        let _ = profiles.save(profiles::PROFILES_PATH);
+                    }
+
+                    if matches!(queue_id, Some(queue_id) if lobby::queue_uses_positions(queue_id)) {
+                        let _ = lobby::set_position_preferences(
+                            &lcu,
+                            &profile.lobby.first_position,
+                            &profile.lobby.second_position,
+                        )
+                        .await;
+                    }
+                }
+            }
+
+            let rune_page = rune_page_request_clone.lock().unwrap().take();
+            if let Some(name) = rune_page {
+                if !matches!(profiles::select_rune_page(&lcu, &name).await, Ok(true)) {
+                    *gameflow_status_clone.lock().unwrap() =
+                        format!("Couldn't switch to the rune page \"{}\"", name);
+                    tokio::time::sleep(tokio::time::Duration::from_secs(2)).await;
+                }
+            }
 
             match phase {
                 Some("Matchmaking") => {
                     *assigned_position.lock().unwrap() = None;
                     *gameflow_status_clone.lock().unwrap() = "Looking for a match".to_owned();
                     locked_champ = false;
+                    hovered_champ = None;
+                    requeued_this_game = false;
+                    accepted_ready_check = false;
                 }
                 Some("Lobby") => {
                     *assigned_position.lock().unwrap() = None;
                     *gameflow_status_clone.lock().unwrap() = "In Lobby".to_owned();
+
+                    // Someone in the lobby declined the ready check we accepted, which kicks the whole lobby out of queue
+                    if auto_requeue && accepted_ready_check {
+                        accepted_ready_check = false;
+                        if lobby::start_matchmaking(&lcu).await.is_ok() {
+                            *gameflow_status_clone.lock().unwrap() =
+                                "Ready check declined, rejoining queue".to_owned();
+                        }
+                    }
                 }
                 Some("ReadyCheck") => {
+                    if phase_changed {
+                        notifier_clone
+                            .notify(notifications::Event::MatchFound, "A match has been found.");
+                        sounds_clone.play(sounds::Cue::ReadyCheck);
+                    }
                     if auto_accept {
                         *gameflow_status_clone.lock().unwrap() = "Accepting match".to_owned();
-                        rest_client
-                            .post(format!(
-                                "https://127.0.0.1:{}/lol-matchmaking/v1/ready-check/accept",
-                                lc_info.port
-                            ))
-                            .send()
-                            .await
-                            .unwrap();
+                        let _ = lcu
+                            .post("/lol-matchmaking/v1/ready-check/accept", None)
+                            .await;
+                    }
+                    let ready_check: serde_json::Value = lcu
+                        .get("/lol-matchmaking/v1/ready-check")
+                        .await
+                        .unwrap_or_default();
+                    let was_accepted = accepted_ready_check;
+                    accepted_ready_check = ready_check["playerResponse"] == "Accepted";
+                    if accepted_ready_check && !was_accepted {
+                        if auto_accept {
+                            notifier_clone.notify(
+                                notifications::Event::MatchAccepted,
+                                "The match has been accepted.",
+                            );
+                        }
+                        webhooks_clone.send(
+                            webhooks::WebhookEvent::MatchAccepted,
+                            &[(
+                                "queue_id",
+                                gameflow["gameData"]["queue"]["id"]
+                                    .as_u64()
+                                    .unwrap_or(0)
+                                    .to_string(),
+                            )],
+                        );
                     }
                     *gameflow_status_clone.lock().unwrap() = "Match Found".to_owned();
                 }
                 Some("ChampSelect") => {
-                    let current_champ_select: serde_json::Value = rest_client
-                        .get(format!(
-                            "https://127.0.0.1:{}/lol-champ-select/v1/session",
-                            lc_info.port
-                        ))
-                        .send()
-                        .await
-                        .unwrap()
-                        .json()
-                        .await
-                        .unwrap();
+                    accepted_ready_check = false;
+
+                    if phase_changed {
+                        locked_champ = false;
+                        hovered_champ = None;
+                        pick_override = false;
+                        ban_override = false;
+                        ban_failure_notified = false;
+                        local_turn = None;
+                        local_locked = false;
+                        spells_override = false;
+                        sent_spells = None;
+                        expected_aram_champ = None;
+                        applied_skin = None;
+                        *current_champion_clone.lock().unwrap() = None;
+                        pending_game = Some(history::GameRecord::default());
+                    }
+
+                    // A session that can't be fetched (or is missing from a replay) is tried again on the next loop
+                    let Ok(current_champ_select) = lcu.get("/lol-champ-select/v1/session").await
+                    else {
+                        tokio::time::sleep(tokio::time::Duration::from_millis(500)).await;
+                        continue;
+                    };
+                    if record_drafts_clone.load(Ordering::SeqCst) {
+                        let _ = draft_recorder.record(
+                            &current_champ_select,
+                            gameflow["gameData"]["queue"]["id"].as_u64().unwrap_or(0) as u32,
+                        );
+                    }
+                    let draft = draft::Draft::from_session(&current_champ_select, lcu.now_ms());
+                    let current_turn = draft
+                        .current_actions
+                        .iter()
+                        .find(|action| action.ally && action.actor_cell_id == draft.local_cell_id)
+                        .map(|action| action.kind.clone());
+                    if current_turn.is_some() && current_turn != local_turn {
+                        sounds_clone.play(sounds::Cue::Turn);
+                    }
+                    local_turn = current_turn;
+                    let local_cell = draft
+                        .my_team
+                        .iter()
+                        .find(|cell| cell.cell_id == draft.local_cell_id);
+                    let locked = matches!(local_cell, Some(cell) if cell.locked);
+                    if let (true, false, Some(cell)) = (locked, local_locked, local_cell) {
+                        sounds_clone.play(sounds::Cue::Lock);
+                        webhooks_clone.send(
+                            webhooks::WebhookEvent::ChampionLocked,
+                            &[
+                                (
+                                    "champion",
+                                    champion_names
+                                        .get(&cell.champion_id)
+                                        .cloned()
+                                        .unwrap_or_else(|| cell.champion_id.to_string()),
+                                ),
+                                ("champion_id", cell.champion_id.to_string()),
+                                ("position", cell.position.clone()),
+                            ],
+                        );
+                    }
+                    local_locked = locked;
+                    *champ_select_clone.lock().unwrap() = Some((draft, std::time::Instant::now()));
 
-                    let team_data_response: Vec<MyTeamData> =
-                        serde_json::from_value(current_champ_select["myTeam"].clone()).unwrap();
+                    let Ok(team_data_response) = serde_json::from_value::<Vec<MyTeamData>>(
+                        current_champ_select["myTeam"].clone(),
+                    ) else {
+                        continue;
+                    };
                     let filtered_team_data: Vec<MyTeamData> = team_data_response
                         .iter()
                         .filter(|data| data.cellId == current_champ_select["localPlayerCellId"])
                         .take(1)
                         .cloned() // Limit to a maximum of 2 matches
                         .collect();
-                    let extracted_team_data: (u32, u32, String) = filtered_team_data
+                    let Some(extracted_team_data) = filtered_team_data
                         .iter()
                         .map(|data| (data.spell1Id, data.spell2Id, data.assignedPosition.clone()))
                         .next()
-                        .unwrap();
+                    else {
+                        continue;
+                    };
 
                     *assigned_position.lock().unwrap() = Some(extracted_team_data.clone().2);
-                    if spell_selection {
+                    if spell_selection && !spells_override {
                         let spell1_clone = selected_image1_clone.lock().unwrap().clone();
                         let spell2_clone = selected_image2_clone.lock().unwrap().clone();
 
@@ -1081,21 +2888,198 @@ async fn main() -> Result<(), Box<dyn Error>> {
                                 .find(|spell| spell.name == spell2_clone.clone().unwrap())
                                 .unwrap();
 
-                            let body = serde_json::json!({
-                                    "spell1Id": spell1_info.key,
-                                    "spell2Id": spell2_info.key
-                            });
+                            let wanted_spells = (spell1_info.key, spell2_info.key);
+                            let current_spells = (extracted_team_data.0, extracted_team_data.1);
 
-                            rest_client
-                                .patch(format!(
-                                    "https://127.0.0.1:{}/lol-champ-select/v1/session/my-selection",
-                                    lc_info.port
-                                ))
-                                .json(&body)
-                                .send()
-                                .await
-                                .unwrap();
+                            if matches!(sent_spells, Some(sent) if sent != current_spells) {
+                                spells_override = true;
+                            } else if current_spells != wanted_spells {
+                                let body = serde_json::json!({
+                                        "spell1Id": wanted_spells.0,
+                                        "spell2Id": wanted_spells.1
+                                });
+
+                                // A failed request is retried on the next loop instead of being taken for a manual change
+                                if lcu
+                                    .patch("/lol-champ-select/v1/session/my-selection", &body)
+                                    .await
+                                    .is_ok()
+                                {
+                                    sent_spells = Some(wanted_spells);
+                                }
+                            } else {
+                                sent_spells = Some(wanted_spells);
+                            }
+                        }
+                    }
+
+                    let local_cell_id = current_champ_select["localPlayerCellId"]
+                        .as_u64()
+                        .unwrap_or(0);
+                    let my_champion = filtered_team_data
+                        .first()
+                        .map(|data| data.championId)
+                        .unwrap_or(0);
+                    if my_champion != 0 {
+                        *current_champion_clone.lock().unwrap() = Some(my_champion);
+                    }
+                    if let Some(game) = pending_game.as_mut() {
+                        game.champion_id = my_champion;
+                        game.position = extracted_team_data.2.clone();
+                        game.spell1_id = extracted_team_data.0;
+                        game.spell2_id = extracted_team_data.1;
+                        game.auto_picked = locked_champ && !pick_override;
+                    }
+
+                    let skin_choice =
+                        if my_champion != 0 && skins::pick_locked(&current_champ_select) {
+                            skin_choices_clone
+                                .lock()
+                                .unwrap()
+                                .get(&my_champion)
+                                .copied()
+                        } else {
+                            None
+                        };
+                    // Keyed by the choice too, so a choice changed after locking is still applied
+                    if let Some(skin_choice) = skin_choice
+                        .filter(|skin_choice| applied_skin != Some((my_champion, *skin_choice)))
+                    {
+                        let cached = champion_skins_clone
+                            .lock()
+                            .unwrap()
+                            .get(&my_champion)
+                            .cloned();
+                        let skins = match cached {
+                            Some(skins) => Some(skins),
+                            None => match skins::fetch_skins(&lcu, my_champion).await {
+                                Ok(skins) => {
+                                    champion_skins_clone
+                                        .lock()
+                                        .unwrap()
+                                        .insert(my_champion, skins.clone());
+                                    Some(skins)
+                                }
+                                Err(_) => None,
+                            },
+                        };
+
+                        // Failed requests leave the skin unapplied so it's retried on the next loop
+                        match skins.map(|skins| skins::choose_skin(skin_choice, &skins)) {
+                            Some(Some(skin_id)) => {
+                                if skins::select_skin(&lcu, skin_id).await.is_ok() {
+                                    applied_skin = Some((my_champion, skin_choice));
+                                }
+                            }
+                            Some(None) => applied_skin = Some((my_champion, skin_choice)),
+                            None => {}
+                        }
+                    }
+
+                    if auto_pick_order_swaps {
+                        for (id, cell_id) in trades::received_requests(
+                            &current_champ_select,
+                            trades::RequestKind::PickOrderSwap,
+                        ) {
+                            let accept = trades::accept_pick_order_swap(
+                                &current_champ_select,
+                                local_cell_id,
+                                cell_id,
+                            );
+                            let _ = trades::respond(
+                                &lcu,
+                                trades::RequestKind::PickOrderSwap,
+                                id,
+                                accept,
+                            )
+                            .await;
+                        }
+                    }
+
+                    if auto_trades {
+                        let priority: Vec<u32> = if current_champ_select["benchEnabled"] == true {
+                            aram_wishlist.iter().map(|(id, _)| *id).collect()
+                        } else {
+                            champion_picks
+                                .iter()
+                                .filter(|(_, name)| !name.is_empty())
+                                .map(|(id, _)| *id)
+                                .collect()
+                        };
+
+                        let mut traded = false;
+                        for (id, cell_id) in trades::received_requests(
+                            &current_champ_select,
+                            trades::RequestKind::Trade,
+                        ) {
+                            let their_champion = team_data_response
+                                .iter()
+                                .find(|data| data.cellId as u64 == cell_id)
+                                .map(|data| data.championId)
+                                .unwrap_or(0);
+                            let accept =
+                                trades::accept_trade(&priority, my_champion, their_champion);
+
+                            let responded =
+                                trades::respond(&lcu, trades::RequestKind::Trade, id, accept)
+                                    .await
+                                    .is_ok();
+                            // Keeps the manual override detection from mistaking the trade for a manual swap
+                            if responded && accept {
+                                expected_aram_champ = Some(their_champion);
+                                traded = true;
+                                break;
+                            }
+                        }
+                        if traded {
+                            continue;
+                        }
+                    }
+
+                    if aram_bench_swap && current_champ_select["benchEnabled"] == true {
+                        // Our champion changed without us swapping or rerolling, so the player did it
+                        if matches!(expected_aram_champ, Some(expected) if expected != my_champion)
+                        {
+                            pick_override = true;
+                        }
+
+                        *gameflow_status_clone.lock().unwrap() = if pick_override {
+                            "ARAM Champion Selection, bench swap paused by manual swap".to_owned()
+                        } else {
+                            "ARAM Champion Selection with bench swap ON".to_owned()
+                        };
+
+                        if pick_override || aram_wishlist.is_empty() {
+                            continue;
+                        }
+
+                        let wishlist: Vec<u32> = aram_wishlist.iter().map(|(id, _)| *id).collect();
+                        let bench = aram::bench_champions(&current_champ_select);
+
+                        if let Some(champion_id) =
+                            aram::best_bench_swap(&wishlist, my_champion, &bench)
+                        {
+                            if aram::swap_with_bench(&lcu, champion_id).await.is_ok() {
+                                expected_aram_champ = Some(champion_id);
+                            }
+                            tokio::time::sleep(tokio::time::Duration::from_secs(1)).await;
+                            continue;
+                        }
+
+                        expected_aram_champ = Some(my_champion);
+                        let rerolls_remaining = current_champ_select["rerollsRemaining"]
+                            .as_u64()
+                            .unwrap_or(0);
+
+                        if aram_reroll && !wishlist.contains(&my_champion) && rerolls_remaining > 0
+                        {
+                            if aram::reroll(&lcu).await.is_ok() {
+                                // The rerolled champion is unknown until the next session update
+                                expected_aram_champ = None;
+                            }
+                            tokio::time::sleep(tokio::time::Duration::from_secs(1)).await;
                         }
+                        continue;
                     }
 
                     if !pick_ban_selection {
@@ -1103,243 +3087,303 @@ async fn main() -> Result<(), Box<dyn Error>> {
                         continue;
                     }
 
-                    *gameflow_status_clone.lock().unwrap() =
-                        "Champion Selection with Auto-pick/ban ON".to_owned();
+                    *gameflow_status_clone.lock().unwrap() = if pick_override {
+                        "Champion Selection, Auto-pick paused by manual pick".to_owned()
+                    } else {
+                        "Champion Selection with Auto-pick/ban ON".to_owned()
+                    };
 
-                    if champion_picks.len() == 0 && ban_picks.is_none() {
+                    if champion_picks.is_empty() && ban_picks.is_empty() {
                         continue;
                     }
 
-                    let current_champ_select: serde_json::Value = rest_client
-                        .get(format!(
-                            "https://127.0.0.1:{}/lol-champ-select/v1/session",
-                            lc_info.port
-                        ))
-                        .send()
-                        .await
-                        .unwrap()
-                        .json()
-                        .await
-                        .unwrap();
+                    let Ok(current_champ_select) = lcu.get("/lol-champ-select/v1/session").await
+                    else {
+                        tokio::time::sleep(tokio::time::Duration::from_millis(500)).await;
+                        continue;
+                    };
 
-                    let action_response: Vec<Vec<ActionResponseData>> =
-                        serde_json::from_value(current_champ_select["actions"].clone()).unwrap();
+                    let Ok(action_response) = serde_json::from_value::<Vec<Vec<ActionResponseData>>>(
+                        current_champ_select["actions"].clone(),
+                    ) else {
+                        continue;
+                    };
                     let filtered_action_data: Vec<ActionResponseData> = action_response
                         .iter()
                         .flatten()
                         .filter(|data| {
                             data.actorCellId == current_champ_select["localPlayerCellId"]
                         })
-                        .take(2) // Limit to a maximum of 2 matches
                         .cloned()
                         .collect();
-                    let extracted_action_data: Vec<(i32, bool, String, bool)> =
+
+                    // Missing actions (e.g. modes without bans) are treated as already completed
+                    let (ban_id, ban_is_in_progress, ban_completed, ban_champion_id) =
                         filtered_action_data
                             .iter()
+                            .find(|data| data.r#type == "ban")
                             .map(|data| {
-                                (
-                                    data.id,
-                                    data.isInProgress,
-                                    data.r#type.clone(),
-                                    data.completed,
-                                )
+                                (data.id, data.isInProgress, data.completed, data.championId)
+                            })
+                            .unwrap_or((0, false, true, 0));
+                    let (pick_id, pick_is_in_progress, pick_completed, pick_champion_id) =
+                        filtered_action_data
+                            .iter()
+                            .find(|data| data.r#type == "pick")
+                            .map(|data| {
+                                (data.id, data.isInProgress, data.completed, data.championId)
                             })
-                            .collect();
+                            .unwrap_or((0, false, true, 0));
 
-                    let (ban_id, ban_is_in_progress, _type1, ban_completed) = extracted_action_data
-                        .get(0)
-                        .cloned()
-                        .unwrap_or((0, false, "".to_string(), false));
-                    let (pick_id, pick_is_in_progress, _type2, pick_completed) =
-                        extracted_action_data.get(1).cloned().unwrap_or((
-                            0,
-                            false,
-                            "".to_string(),
-                            false,
-                        ));
+                    // Bans are locked as soon as they're sent, so any hovered ban comes from the player
+                    if ban_is_in_progress && !ban_completed && ban_champion_id != 0 {
+                        ban_override = true;
+                    }
+                    if !locked_champ
+                        && pick_champion_id != 0
+                        && Some(pick_champion_id) != hovered_champ
+                    {
+                        pick_override = true;
+                    }
+
+                    if ban_is_in_progress
+                        && !ban_completed
+                        && !ban_override
+                        && current_champ_select["timer"]["phase"] != "PLANNING"
+                    {
+                        // Champions our allies want to play, the local player's own intent doesn't count
+                        let Ok(team_data) = serde_json::from_value::<Vec<MyTeamData>>(
+                            current_champ_select["myTeam"].clone(),
+                        ) else {
+                            continue;
+                        };
+                        let ally_intents: Vec<u32> = if respect_ally_hovers {
+                            team_data
+                                .iter()
+                                .filter(|data| {
+                                    data.cellId != current_champ_select["localPlayerCellId"]
+                                })
+                                .map(|data| data.championPickIntent)
+                                .filter(|champion_id| *champion_id != 0)
+                                .collect()
+                        } else {
+                            Vec::new()
+                        };
+
+                        // Skip entries and ally intents are left out on purpose, only the others count as candidates
+                        let mut candidates = 0;
+                        let mut ban_tried = false;
+                        for (champion_id, name) in &ban_picks {
+                            if name.is_empty() || ally_intents.contains(champion_id) {
+                                continue;
+                            }
+                            candidates += 1;
+                            let ban_champ_info: serde_json::Value = lcu
+                                .get(&format!(
+                                    "/lol-champ-select/v1/grid-champions/{}",
+                                    champion_id
+                                ))
+                                .await
+                                .unwrap_or_default();
+
+                            if ban_champ_info["selectionStatus"]["pickedByOtherOrBanned"] == true {
+                                continue;
+                            }
 
-                    if ban_picks.is_some() {
-                        if !ban_picks.as_ref().unwrap().1.is_empty() {
                             let ban_body = serde_json::json!({
                                     "actorCellId": current_champ_select["localPlayerCellId"],
-                                    "championId": &ban_picks.as_ref().unwrap().0,
+                                    "championId": champion_id,
                                     "completed": true,
                                     "id": &ban_id,
                                     "isAllyAction": true,
                                     "type": "ban"
                             });
-                            let ban_champ_info: serde_json::Value = rest_client
-                                .get(format!(
-                                    "https://127.0.0.1:{}/lol-champ-select/v1/grid-champions/{}",
-                                    lc_info.port,
-                                    &ban_picks.as_ref().unwrap().0
-                                ))
-                                .send()
-                                .await
-                                .unwrap()
-                                .json()
-                                .await
-                                .unwrap();
 
-                            if ban_is_in_progress
-                                && !ban_completed
-                                && ban_champ_info["selectionStatus"]["pickedByOtherOrBanned"]
-                                    != true
-                                && current_champ_select["timer"]["phase"] != "PLANNING"
-                            {
-                                rest_client
-                                    .patch(format!(
-                                    "https://127.0.0.1:{}/lol-champ-select/v1/session/actions/{}",
-                                    lc_info.port, ban_id
-                                ))
-                                    .json(&ban_body)
-                                    .send()
-                                    .await
-                                    .unwrap();
-                                tokio::time::sleep(tokio::time::Duration::from_secs(10)).await;
+                            ban_tried = true;
+                            let ban_sent = lcu
+                                .patch(
+                                    &format!("/lol-champ-select/v1/session/actions/{}", ban_id),
+                                    &ban_body,
+                                )
+                                .await
+                                .is_ok();
+                            if !ban_sent && !ban_failure_notified {
+                                notifier_clone.notify(
+                                    notifications::Event::BanFailed,
+                                    format!("Couldn't ban {}.", name),
+                                );
+                                ban_failure_notified = true;
                             }
+                            tokio::time::sleep(tokio::time::Duration::from_secs(10)).await;
+                            break;
                         }
-                    }
-
-                    if champion_picks.len() != 0 {
-                        if champion_picks.get(0).unwrap().1.is_empty()
-                            && champion_picks.get(1).unwrap().1.is_empty()
-                        {
-                            continue;
+                        if !ban_tried && !ban_failure_notified && candidates > 0 {
+                            notifier_clone.notify(
+                                notifications::Event::BanFailed,
+                                "None of the champions on your ban list could be banned.",
+                            );
+                            ban_failure_notified = true;
                         }
-                        if !champion_picks.get(0).unwrap().1.is_empty() {
-                            let pick_champ_info: serde_json::Value = rest_client
-                                .get(format!(
-                                    "https://127.0.0.1:{}/lol-champ-select/v1/grid-champions/{}",
-                                    lc_info.port,
-                                    champion_picks.get(0).unwrap().0
-                                ))
-                                .send()
-                                .await
-                                .unwrap()
-                                .json()
-                                .await
-                                .unwrap();
+                    }
 
-                            let pick_body = serde_json::json!({
-                                    "actorCellId": current_champ_select["localPlayerCellId"],
-                                    "championId": champion_picks.get(0).unwrap().0,
-                                    "completed": true,
-                                    "id": &pick_id,
-                                    "isAllyAction": true,
-                                    "type": "pick"
-                            });
+                    // The late lock-in guarantee still applies to whatever the player hovered
+                    if pick_completed || locked_champ || (pick_override && !late_lock) {
+                        continue;
+                    }
 
-                            if !pick_is_in_progress
-                                && pick_completed
-                                && !ban_is_in_progress
-                                && ban_completed
-                                || current_champ_select["timer"]["phase"] == "PLANNING"
-                            {
-                                continue;
-                            }
+                    let mut pick_pool: Vec<u32> = if pick_override {
+                        vec![pick_champion_id]
+                    } else {
+                        champion_picks
+                            .iter()
+                            .filter(|(_, name)| !name.is_empty())
+                            .map(|(champion_id, _)| *champion_id)
+                            .collect()
+                    };
+                    pick_pool.retain(|champion_id| {
+                        *champion_id != 0
+                            && (champion_availability.is_empty()
+                                || ownership::Availability::of(
+                                    &champion_availability,
+                                    *champion_id,
+                                )
+                                .can_pick())
+                    });
+                    if counter_pick && !pick_override {
+                        let (enemies, lane_opponent) = counters::enemy_champions(
+                            &current_champ_select,
+                            &extracted_team_data.2,
+                        );
+                        pick_pool = counters::order_by_counters(
+                            &pick_pool,
+                            &counter_table,
+                            &enemies,
+                            lane_opponent,
+                        );
+                    }
 
-                            if !pick_is_in_progress {
-                                continue;
-                            }
-                            if pick_champ_info["selectionStatus"]["pickedByOtherOrBanned"] != true {
-                                if pick_is_in_progress
-                                    && !pick_completed
-                                    && !ban_is_in_progress
-                                    && ban_completed
-                                    && pick_champ_info["selectionStatus"]["pickedByOtherOrBanned"]
-                                        != true
-                                    && !locked_champ
-                                {
-                                    if rune_change {
-                                        // TODO:
-                                    }
-                                    rest_client
-                                        .patch(format!(
-                                    "https://127.0.0.1:{}/lol-champ-select/v1/session/actions/{}",
-                                    lc_info.port, pick_id
-                                ))
-                                        .json(&pick_body)
-                                        .send()
-                                        .await
-                                        .unwrap();
-                                    locked_champ = true;
-                                    tokio::time::sleep(tokio::time::Duration::from_secs(1)).await;
-                                }
-                            }
-                        }
+                    // The first champion in the pick pool that nobody has picked or banned yet
+                    let mut available_pick = None;
+                    for champion_id in pick_pool {
+                        let pick_champ_info: serde_json::Value = lcu
+                            .get(&format!(
+                                "/lol-champ-select/v1/grid-champions/{}",
+                                champion_id
+                            ))
+                            .await
+                            .unwrap_or_default();
 
-                        if champion_picks.len() == 1 {
-                            continue;
+                        if pick_champ_info["selectionStatus"]["pickedByOtherOrBanned"] != true {
+                            available_pick = Some(champion_id);
+                            break;
                         }
+                    }
 
-                        if !champion_picks.get(1).unwrap().1.is_empty() {
-                            let pick_champ_info: serde_json::Value = rest_client
-                                .get(format!(
-                                    "https://127.0.0.1:{}/lol-champ-select/v1/grid-champions/{}",
-                                    lc_info.port,
-                                    champion_picks.get(1).unwrap().0
-                                ))
-                                .send()
-                                .await
-                                .unwrap()
-                                .json()
-                                .await
-                                .unwrap();
-
-                            let pick_body = serde_json::json!({
+                    let Some(champion_id) = available_pick else {
+                        continue;
+                    };
+
+                    let our_turn = pick_is_in_progress && !ban_is_in_progress && ban_completed;
+                    let lock_now = our_turn
+                        && (!late_lock
+                            || draft::late_lock_due(
+                                &current_champ_select["timer"],
+                                lcu.now_ms(),
+                                late_lock_seconds,
+                            ));
+
+                    if !lock_now {
+                        // Hovering shows our pick intent to the rest of the team without locking it
+                        let can_hover = !pick_override
+                            && (current_champ_select["timer"]["phase"] == "PLANNING" || our_turn);
+                        if can_hover && hovered_champ != Some(champion_id) {
+                            let hover_body = serde_json::json!({
                                     "actorCellId": current_champ_select["localPlayerCellId"],
-                                    "championId": champion_picks.get(1).unwrap().0,
-                                    "completed": true,
+                                    "championId": champion_id,
                                     "id": &pick_id,
                                     "isAllyAction": true,
                                     "type": "pick"
                             });
 
-                            if !pick_is_in_progress
-                                && pick_completed
-                                && !ban_is_in_progress
-                                && ban_completed
-                                || current_champ_select["timer"]["phase"] == "PLANNING"
+                            if lcu
+                                .patch(
+                                    &format!("/lol-champ-select/v1/session/actions/{}", pick_id),
+                                    &hover_body,
+                                )
+                                .await
+                                .is_ok()
                             {
-                                continue;
-                            }
-
-                            if !pick_is_in_progress {
-                                continue;
-                            }
-                            if pick_champ_info["selectionStatus"]["pickedByOtherOrBanned"] != true {
-                                if pick_is_in_progress
-                                    && !pick_completed
-                                    && !ban_is_in_progress
-                                    && ban_completed
-                                    && pick_champ_info["selectionStatus"]["pickedByOtherOrBanned"]
-                                        != true
-                                    && !locked_champ
-                                {
-                                    if rune_change {
-                                        // TODO:
-                                    }
-                                    rest_client
-                                        .patch(format!(
-                                    "https://127.0.0.1:{}/lol-champ-select/v1/session/actions/{}",
-                                    lc_info.port, pick_id
-                                ))
-                                        .json(&pick_body)
-                                        .send()
-                                        .await
-                                        .unwrap();
-                                    locked_champ = true;
-                                    tokio::time::sleep(tokio::time::Duration::from_secs(1)).await;
-                                }
+                                hovered_champ = Some(champion_id);
                             }
                         }
+                        continue;
                     }
+
+                    if rune_change {
+                        // TODO:
+                    }
+                    // With late lock-in the player may have hovered something else in the meantime, lock that instead
+                    let lock_champion_id = if late_lock && pick_champion_id != 0 {
+                        pick_champion_id
+                    } else {
+                        champion_id
+                    };
+                    let pick_body = serde_json::json!({
+                            "actorCellId": current_champ_select["localPlayerCellId"],
+                            "championId": lock_champion_id,
+                            "completed": true,
+                            "id": &pick_id,
+                            "isAllyAction": true,
+                            "type": "pick"
+                    });
+
+                    let locked = lcu
+                        .patch(
+                            &format!("/lol-champ-select/v1/session/actions/{}", pick_id),
+                            &pick_body,
+                        )
+                        .await
+                        .is_ok();
+                    if locked {
+                        notifier_clone.notify(
+                            notifications::Event::ChampionLocked,
+                            format!(
+                                "Locked in {}.",
+                                champion_names
+                                    .get(&lock_champion_id)
+                                    .map(String::as_str)
+                                    .unwrap_or("your champion")
+                            ),
+                        );
+                    }
+                    locked_champ = true;
+                    tokio::time::sleep(tokio::time::Duration::from_secs(1)).await;
                 }
                 Some("InProgress") => {
                     *gameflow_status_clone.lock().unwrap() = "Game in progress...".to_owned();
                     tokio::time::sleep(tokio::time::Duration::from_secs(20)).await;
                 }
+                // Only once the game is recorded, leaving the post game screen earlier loses its result
+                Some("EndOfGame")
+                    if auto_requeue
+                        && game_ended.is_none()
+                        && !requeued_this_game
+                        && (requeue_max_games == 0 || requeued_games < requeue_max_games) =>
+                {
+                    *assigned_position.lock().unwrap() = None;
+                    *gameflow_status_clone.lock().unwrap() =
+                        format!("Rejoining queue in {} seconds", requeue_delay);
+                    tokio::time::sleep(tokio::time::Duration::from_secs(requeue_delay)).await;
+
+                    let rejoined = lobby::play_again(&lcu).await.is_ok()
+                        && lobby::start_matchmaking(&lcu).await.is_ok();
+                    if rejoined {
+                        requeued_this_game = true;
+                        requeued_games += 1;
+                    } else {
+                        *gameflow_status_clone.lock().unwrap() = "Couldn't rejoin queue".to_owned();
+                    }
+                }
                 Some("WaitingForStats") => {
                     *gameflow_status_clone.lock().unwrap() = "Waiting for Stats".to_owned();
                     tokio::time::sleep(tokio::time::Duration::from_secs(2)).await;
@@ -1366,7 +3410,7 @@ async fn main() -> Result<(), Box<dyn Error>> {
         }
     });
 
-    eframe::run_native("Circuit Watcher", options, Box::new(|_cc| Box::new(app)))?;
+    eframe::run_native(tray::WINDOW_TITLE, options, Box::new(|_cc| Box::new(app)))?;
 
     Ok(())
 }
@@ -0,0 +1,97 @@
+use serde::{Deserialize, Serialize};
+use std::collections::{HashSet, VecDeque};
+use std::sync::{Arc, Mutex};
+
+/// File the events notifications are turned on for are saved to.
+pub const NOTIFICATIONS_PATH: &str = "./utils/notifications.json";
+
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq, Eq, Hash)]
+/// Events a desktop notification can be shown for.
+pub enum Event {
+    MatchFound,
+    MatchAccepted,
+    ChampionLocked,
+    BanFailed,
+    Disconnected,
+    UpdateAvailable,
+}
+
+impl Event {
+    pub const ALL: [Event; 6] = [
+        Event::MatchFound,
+        Event::MatchAccepted,
+        Event::ChampionLocked,
+        Event::BanFailed,
+        Event::Disconnected,
+        Event::UpdateAvailable,
+    ];
+
+    /// The name of the event, also used as the title of its notifications.
+    pub fn label(&self) -> &'static str {
+        match self {
+            Event::MatchFound => "Match found",
+            Event::MatchAccepted => "Match accepted",
+            Event::ChampionLocked => "Champion locked",
+            Event::BanFailed => "Ban failed",
+            Event::Disconnected => "LeagueClient disconnected",
+            Event::UpdateAvailable => "Update available",
+        }
+    }
+}
+
+#[derive(Clone, Default)]
+/// The `Notifier` struct queues desktop notifications for the events they're turned on for.
+/// The tray icon shows the queued notifications.
+///
+/// ### Properties:
+/// * `enabled`: The events notifications are shown for, none by default.
+/// * `queue`: Notifications waiting to be shown, as `(title, message)` pairs.
+pub struct Notifier {
+    pub enabled: Arc<Mutex<HashSet<Event>>>,
+    pub queue: Arc<Mutex<VecDeque<(String, String)>>>,
+}
+
+impl Notifier {
+    /// Reads the events notifications are turned on for, none if the file doesn't exist or can't be parsed.
+    pub fn load(path: &str) -> Self {
+        let enabled: HashSet<Event> = std::fs::read_to_string(path)
+            .ok()
+            .and_then(|data| serde_json::from_str(&data).ok())
+            .unwrap_or_default();
+        Self {
+            enabled: Arc::new(Mutex::new(enabled)),
+            ..Default::default()
+        }
+    }
+
+    pub fn save(&self, path: &str) -> std::io::Result<()> {
+        let enabled = self.enabled.lock().unwrap().clone();
+        std::fs::write(path, serde_json::to_string_pretty(&enabled)?)
+    }
+
+    pub fn is_enabled(&self, event: Event) -> bool {
+        self.enabled.lock().unwrap().contains(&event)
+    }
+
+    pub fn toggle(&self, event: Event) {
+        let mut enabled = self.enabled.lock().unwrap();
+        if !enabled.remove(&event) {
+            enabled.insert(event);
+        }
+    }
+
+    /// Queues a notification if it's turned on for the event.
+    pub fn notify(&self, event: Event, message: impl Into<String>) {
+        if self.is_enabled(event) {
+            self.show(event.label(), message);
+        }
+    }
+
+    /// Queues a notification whatever the events turned on, for confirmations of something the user just did.
+    pub fn show(&self, title: impl Into<String>, message: impl Into<String>) {
+        self.queue
+            .lock()
+            .unwrap()
+            .push_back((title.into(), message.into()));
+    }
+}
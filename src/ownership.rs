@@ -0,0 +1,92 @@
+use serde::Deserialize;
+use std::collections::HashMap;
+
+#[allow(non_snake_case)]
+#[derive(Debug, Deserialize)]
+/// The `MinimalChampion` struct is used for deserializing `/lol-champions/v1/owned-champions-minimal`.
+///
+/// ### Properties:
+/// * `id`: The champion id.
+/// * `active`: Whether the champion is enabled, champions get disabled when they're bugged. Enabled if missing.
+/// * `freeToPlay`: Whether the champion is in the free rotation.
+/// * `ownership`: Whether the player owns (or rents) the champion.
+struct MinimalChampion {
+    id: i32,
+    #[serde(default = "enabled")]
+    active: bool,
+    #[serde(default)]
+    freeToPlay: bool,
+    ownership: MinimalOwnership,
+}
+
+fn enabled() -> bool {
+    true
+}
+
+#[derive(Debug, Deserialize)]
+struct MinimalOwnership {
+    #[serde(default)]
+    owned: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+/// Whether the player can pick a champion.
+pub enum Availability {
+    Owned,
+    FreeRotation,
+    Unowned,
+    Disabled,
+}
+
+impl Availability {
+    /// Looks up a champion, champions missing from the owned list aren't owned.
+    pub fn of(availability: &HashMap<u32, Availability>, champion_id: u32) -> Availability {
+        availability
+            .get(&champion_id)
+            .copied()
+            .unwrap_or(Availability::Unowned)
+    }
+
+    pub fn can_pick(&self) -> bool {
+        matches!(self, Availability::Owned | Availability::FreeRotation)
+    }
+
+    /// Short tag shown next to champion names, empty for owned champions.
+    pub fn tag(&self) -> &'static str {
+        match self {
+            Availability::Owned => "",
+            Availability::FreeRotation => " (free rotation)",
+            Availability::Unowned => " (not owned)",
+            Availability::Disabled => " (disabled)",
+        }
+    }
+}
+
+/// Fetches which champions the player can pick, keyed by champion id.
+///
+/// ### Arguments:
+/// * `lcu`: The client the LeagueClient requests go through.
+pub async fn fetch_availability(
+    lcu: &crate::lcu::LcuClient,
+) -> Result<HashMap<u32, Availability>, crate::lcu::Error> {
+    let champions: Vec<MinimalChampion> =
+        serde_json::from_value(lcu.get("/lol-champions/v1/owned-champions-minimal").await?)?;
+
+    Ok(champions
+        .iter()
+        // Skips placeholder entries that aren't real champions
+        .filter(|champion| champion.id > 0)
+        .map(|champion| {
+            let availability = if !champion.active {
+                Availability::Disabled
+            } else if champion.ownership.owned {
+                Availability::Owned
+            } else if champion.freeToPlay {
+                Availability::FreeRotation
+            } else {
+                Availability::Unowned
+            };
+            (champion.id as u32, availability)
+        })
+        .collect())
+}
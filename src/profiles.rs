@@ -0,0 +1,521 @@
+use base64::Engine;
+use serde::{Deserialize, Serialize};
+use std::sync::{
+    atomic::{AtomicBool, Ordering},
+    Arc, Mutex,
+};
+
+/// File the profiles and the active profile are saved to.
+pub const PROFILES_PATH: &str = "./utils/profiles.json";
+/// Directory profiles are exported to as files.
+pub const EXPORT_DIR: &str = "./utils/profiles";
+/// Version of the profile strings written by [`export_string`], strings from newer versions are refused.
+pub const SHARE_VERSION: u32 = 1;
+
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+/// The `Profile` struct is a named set of settings that can be switched to as a whole.
+///
+/// ### Properties:
+/// * `name`: The name shown in the profile dropdown, unique among the profiles.
+/// * `queue_ids`: Queues the profile is switched to automatically when a lobby for them is entered.
+/// * `auto_accept`: Whether ready checks are accepted.
+/// * `pick_ban`: Whether auto-pick/ban is on.
+/// * `picks`: The pick list, as `(championId, name)` pairs.
+/// * `bans`: The ban list, as `(championId, name)` pairs.
+/// * `spell_selection`: Whether summoner spells are selected automatically.
+/// * `spell1`: The name of the first summoner spell.
+/// * `spell2`: The name of the second summoner spell.
+/// * `rune_page`: The name of the rune page made current when the profile is applied, empty to keep the current one.
+/// * `lobby`: The queue and role preferences used when setting up a lobby.
+pub struct Profile {
+    pub name: String,
+    #[serde(default)]
+    pub queue_ids: Vec<u32>,
+    pub auto_accept: bool,
+    pub pick_ban: bool,
+    pub picks: Vec<(u32, String)>,
+    pub bans: Vec<(u32, String)>,
+    pub spell_selection: bool,
+    pub spell1: Option<String>,
+    pub spell2: Option<String>,
+    #[serde(default)]
+    pub rune_page: String,
+    #[serde(default)]
+    pub lobby: crate::lobby::LobbySettings,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, Default)]
+/// The `Profiles` struct holds every profile and which one is active, as saved in [`PROFILES_PATH`].
+pub struct Profiles {
+    pub active: Option<String>,
+    pub profiles: Vec<Profile>,
+}
+
+impl Profiles {
+    /// Reads the profiles file, no profiles if it doesn't exist or can't be parsed.
+    pub fn load(path: &str) -> Self {
+        std::fs::read_to_string(path)
+            .ok()
+            .and_then(|data| serde_json::from_str(&data).ok())
+            .unwrap_or_default()
+    }
+
+    pub fn save(&self, path: &str) -> std::io::Result<()> {
+        std::fs::write(path, serde_json::to_string_pretty(self)?)
+    }
+
+    pub fn get(&self, name: &str) -> Option<&Profile> {
+        self.profiles.iter().find(|profile| profile.name == name)
+    }
+
+    pub fn get_mut(&mut self, name: &str) -> Option<&mut Profile> {
+        self.profiles
+            .iter_mut()
+            .find(|profile| profile.name == name)
+    }
+
+    pub fn active(&self) -> Option<&Profile> {
+        self.active.as_deref().and_then(|name| self.get(name))
+    }
+
+    /// The first profile set to be used for the given queue.
+    pub fn for_queue(&self, queue_id: u32) -> Option<&Profile> {
+        self.profiles
+            .iter()
+            .find(|profile| profile.queue_ids.contains(&queue_id))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+/// Profile changes requested from the top bar, handled once the frame is drawn.
+pub enum ProfileAction {
+    /// Applies the profile with the given name.
+    Switch(String),
+    /// Creates a profile with the given name out of the current settings and makes it active.
+    Create(String),
+    /// Overwrites the active profile with the current settings.
+    Save,
+    /// Deletes the active profile, the current settings are kept.
+    Delete,
+}
+
+#[derive(Clone)]
+/// The `LiveSettings` struct holds the settings shared between the GUI and the worker that make up a profile.
+///
+/// ### Properties:
+/// * `auto_accept` to `lobby_settings`: The settings of the same name used by the GUI and the worker.
+/// * `rune_page_request`: The rune page the worker has to make current, set when a profile is applied.
+pub struct LiveSettings {
+    pub auto_accept: Arc<AtomicBool>,
+    pub pick_ban_selection: Arc<AtomicBool>,
+    pub champion_picks: Arc<Mutex<Vec<(u32, String)>>>,
+    pub ban_picks: Arc<Mutex<Vec<(u32, String)>>>,
+    pub spell_selection: Arc<AtomicBool>,
+    pub spell1: Arc<Mutex<Option<String>>>,
+    pub spell2: Arc<Mutex<Option<String>>>,
+    pub lobby_settings: Arc<Mutex<crate::lobby::LobbySettings>>,
+    pub rune_page_request: Arc<Mutex<Option<String>>>,
+}
+
+impl LiveSettings {
+    /// Replaces the current settings with the ones of the profile.
+    pub fn apply(&self, profile: &Profile) {
+        self.auto_accept
+            .store(profile.auto_accept, Ordering::SeqCst);
+        self.pick_ban_selection
+            .store(profile.pick_ban, Ordering::SeqCst);
+        *self.champion_picks.lock().unwrap() = profile.picks.clone();
+        *self.ban_picks.lock().unwrap() = profile.bans.clone();
+        self.spell_selection
+            .store(profile.spell_selection, Ordering::SeqCst);
+        *self.spell1.lock().unwrap() = profile.spell1.clone();
+        *self.spell2.lock().unwrap() = profile.spell2.clone();
+        *self.lobby_settings.lock().unwrap() = profile.lobby.clone();
+        if !profile.rune_page.is_empty() {
+            *self.rune_page_request.lock().unwrap() = Some(profile.rune_page.clone());
+        }
+    }
+
+    /// Copies the current settings into the profile, its name, queues and rune page are kept.
+    pub fn capture(&self, profile: &mut Profile) {
+        profile.auto_accept = self.auto_accept.load(Ordering::SeqCst);
+        profile.pick_ban = self.pick_ban_selection.load(Ordering::SeqCst);
+        profile.picks = self.champion_picks.lock().unwrap().clone();
+        profile.bans = self.ban_picks.lock().unwrap().clone();
+        profile.spell_selection = self.spell_selection.load(Ordering::SeqCst);
+        profile.spell1 = self.spell1.lock().unwrap().clone();
+        profile.spell2 = self.spell2.lock().unwrap().clone();
+        profile.lobby = self.lobby_settings.lock().unwrap().clone();
+    }
+
+    /// Creates a profile with the given name out of the current settings.
+    pub fn to_profile(&self, name: &str) -> Profile {
+        let mut profile = Profile {
+            name: name.to_owned(),
+            queue_ids: Vec::new(),
+            auto_accept: false,
+            pick_ban: false,
+            picks: Vec::new(),
+            bans: Vec::new(),
+            spell_selection: false,
+            spell1: None,
+            spell2: None,
+            rune_page: String::new(),
+            lobby: Default::default(),
+        };
+        self.capture(&mut profile);
+        profile
+    }
+}
+
+/// Makes the rune page with the given name the current one, `false` if the player has no page with that name.
+///
+/// ### Arguments:
+/// * `lcu`: The client the LeagueClient requests go through.
+/// * `name`: The name of the rune page.
+pub async fn select_rune_page(
+    lcu: &crate::lcu::LcuClient,
+    name: &str,
+) -> Result<bool, crate::lcu::Error> {
+    let pages = lcu.get("/lol-perks/v1/pages").await?;
+    let page_id = pages
+        .as_array()
+        .into_iter()
+        .flatten()
+        .find(|page| page["name"] == name)
+        .and_then(|page| page["id"].as_u64());
+
+    match page_id {
+        Some(page_id) => {
+            lcu.put("/lol-perks/v1/currentpage", &serde_json::json!(page_id))
+                .await?;
+            Ok(true)
+        }
+        None => Ok(false),
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+/// The `SharedProfile` struct is the part of a profile that is shared with other players.
+/// Champions and summoner spells are only stored as ids, queues and the rune page are personal and left out.
+struct SharedProfile {
+    version: u32,
+    name: String,
+    auto_accept: bool,
+    pick_ban: bool,
+    picks: Vec<u32>,
+    bans: Vec<u32>,
+    spell_selection: bool,
+    spell1: Option<u32>,
+    spell2: Option<u32>,
+    lobby: crate::lobby::LobbySettings,
+}
+
+#[derive(Debug)]
+/// Errors returned when a profile string can't be read.
+pub enum ImportError {
+    /// The file the profile was imported from couldn't be read.
+    File(std::io::Error),
+    /// The string isn't valid base64.
+    Encoding(base64::DecodeError),
+    /// The decoded string isn't a profile.
+    Format(serde_json::Error),
+    /// The profile was exported by a newer version of the app.
+    Version(u32),
+}
+
+impl std::fmt::Display for ImportError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ImportError::File(error) => write!(f, "couldn't read the file: {}", error),
+            ImportError::Encoding(error) => write!(f, "not a profile string: {}", error),
+            ImportError::Format(error) => write!(f, "not a valid profile: {}", error),
+            ImportError::Version(version) => write!(
+                f,
+                "profile version {} is newer than the supported version {}, update the app",
+                version, SHARE_VERSION
+            ),
+        }
+    }
+}
+
+impl std::error::Error for ImportError {}
+
+/// The `Imported` struct is a profile read from a profile string, along with what couldn't be imported.
+///
+/// ### Properties:
+/// * `profile`: The imported profile, without the unknown entries.
+/// * `unknown_champions`: Champion ids that aren't in `champions.json`.
+/// * `unknown_spells`: Summoner spell keys that aren't in `summoner_spells.json`.
+pub struct Imported {
+    pub profile: Profile,
+    pub unknown_champions: Vec<u32>,
+    pub unknown_spells: Vec<u32>,
+}
+
+impl Imported {
+    /// Describes the import, listing the entries that were left out.
+    pub fn report(&self) -> String {
+        let list = |ids: &[u32]| {
+            ids.iter()
+                .map(|id| id.to_string())
+                .collect::<Vec<String>>()
+                .join(", ")
+        };
+
+        let mut report = format!("Imported \"{}\".", self.profile.name);
+        if !self.unknown_champions.is_empty() {
+            report.push_str(&format!(
+                "\nUnknown champion ids left out: {}",
+                list(&self.unknown_champions)
+            ));
+        }
+        if !self.unknown_spells.is_empty() {
+            report.push_str(&format!(
+                "\nUnknown summoner spell keys left out: {}",
+                list(&self.unknown_spells)
+            ));
+        }
+        report
+    }
+}
+
+/// Writes a profile as a base64 encoded JSON string that can be pasted by other players.
+///
+/// ### Arguments:
+/// * `profile`: The profile to export.
+/// * `spell_key`: Returns the key of a summoner spell from its name.
+pub fn export_string(profile: &Profile, spell_key: impl Fn(&str) -> Option<u32>) -> String {
+    let shared = SharedProfile {
+        version: SHARE_VERSION,
+        name: profile.name.clone(),
+        auto_accept: profile.auto_accept,
+        pick_ban: profile.pick_ban,
+        picks: profile.picks.iter().map(|(id, _)| *id).collect(),
+        bans: profile.bans.iter().map(|(id, _)| *id).collect(),
+        spell_selection: profile.spell_selection,
+        spell1: profile.spell1.as_deref().and_then(&spell_key),
+        spell2: profile.spell2.as_deref().and_then(&spell_key),
+        lobby: profile.lobby.clone(),
+    };
+
+    base64::engine::general_purpose::STANDARD
+        .encode(serde_json::to_string(&shared).unwrap_or_default())
+}
+
+/// Writes the profile string of a profile to [`EXPORT_DIR`], returning the path of the file.
+pub fn export_file(name: &str, profile_string: &str) -> std::io::Result<String> {
+    let file_name: String = name
+        .chars()
+        .map(|c| if c.is_alphanumeric() { c } else { '_' })
+        .collect();
+    let path = format!("{}/{}.txt", EXPORT_DIR, file_name);
+
+    std::fs::create_dir_all(EXPORT_DIR)?;
+    std::fs::write(&path, profile_string)?;
+    Ok(path)
+}
+
+/// Reads a profile string, or the file it was exported to, checking every champion and summoner spell exists.
+/// Unknown champions and spells are left out of the profile and listed in the result.
+///
+/// ### Arguments:
+/// * `input`: A profile string or the path of a file containing one.
+/// * `champion_name`: Returns the name of a champion from its id, `None` if it isn't in `champions.json`.
+/// * `spell_name`: Returns the name of a summoner spell from its key, `None` if it isn't in `summoner_spells.json`.
+pub fn import(
+    input: &str,
+    champion_name: impl Fn(u32) -> Option<String>,
+    spell_name: impl Fn(u32) -> Option<String>,
+) -> Result<Imported, ImportError> {
+    let input = input.trim();
+    let text = if std::path::Path::new(input).is_file() {
+        std::fs::read_to_string(input).map_err(ImportError::File)?
+    } else {
+        input.to_owned()
+    };
+
+    let bytes = base64::engine::general_purpose::STANDARD
+        .decode(text.trim())
+        .map_err(ImportError::Encoding)?;
+    let json: serde_json::Value = serde_json::from_slice(&bytes).map_err(ImportError::Format)?;
+    let version = json["version"].as_u64().unwrap_or(0) as u32;
+    if version > SHARE_VERSION {
+        return Err(ImportError::Version(version));
+    }
+    let shared: SharedProfile = serde_json::from_value(json).map_err(ImportError::Format)?;
+
+    let mut unknown_champions = Vec::new();
+    let mut unknown_spells = Vec::new();
+    // Champion id 0 is a skipped pick or ban
+    let mut champions = |ids: &[u32]| -> Vec<(u32, String)> {
+        ids.iter()
+            .filter_map(|id| match (*id, champion_name(*id)) {
+                (0, _) => Some((0, String::new())),
+                (id, Some(name)) => Some((id, name)),
+                (id, None) => {
+                    unknown_champions.push(id);
+                    None
+                }
+            })
+            .collect()
+    };
+    let picks = champions(&shared.picks);
+    let bans = champions(&shared.bans);
+    let mut spell = |key: Option<u32>| -> Option<String> {
+        let key = key?;
+        let name = spell_name(key);
+        if name.is_none() {
+            unknown_spells.push(key);
+        }
+        name
+    };
+    let spell1 = spell(shared.spell1);
+    let spell2 = spell(shared.spell2);
+
+    Ok(Imported {
+        profile: Profile {
+            name: shared.name,
+            queue_ids: Vec::new(),
+            auto_accept: shared.auto_accept,
+            pick_ban: shared.pick_ban,
+            picks,
+            bans,
+            spell_selection: shared.spell_selection,
+            spell1,
+            spell2,
+            rune_page: String::new(),
+            lobby: shared.lobby,
+        },
+        unknown_champions,
+        unknown_spells,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn champion_name(id: u32) -> Option<String> {
+        match id {
+            1 => Some("Annie".to_owned()),
+            22 => Some("Ashe".to_owned()),
+            _ => None,
+        }
+    }
+
+    fn spell_name(key: u32) -> Option<String> {
+        match key {
+            4 => Some("Flash".to_owned()),
+            7 => Some("Heal".to_owned()),
+            _ => None,
+        }
+    }
+
+    fn spell_key(name: &str) -> Option<u32> {
+        (1..=32).find(|key| spell_name(*key).as_deref() == Some(name))
+    }
+
+    fn profile() -> Profile {
+        Profile {
+            name: "Bot lane".to_owned(),
+            queue_ids: vec![420],
+            auto_accept: true,
+            pick_ban: true,
+            picks: vec![(22, "Ashe".to_owned()), (0, String::new())],
+            bans: vec![(1, "Annie".to_owned())],
+            spell_selection: true,
+            spell1: Some("Flash".to_owned()),
+            spell2: Some("Heal".to_owned()),
+            rune_page: "ADC".to_owned(),
+            lobby: Default::default(),
+        }
+    }
+
+    fn encode(shared: serde_json::Value) -> String {
+        base64::engine::general_purpose::STANDARD.encode(shared.to_string())
+    }
+
+    #[test]
+    fn export_and_import_round_trip() {
+        let exported = export_string(&profile(), spell_key);
+        let imported = import(&exported, champion_name, spell_name).unwrap();
+
+        // Queues and the rune page are personal and aren't shared
+        assert_eq!(
+            imported.profile,
+            Profile {
+                queue_ids: Vec::new(),
+                rune_page: String::new(),
+                ..profile()
+            }
+        );
+        assert!(imported.unknown_champions.is_empty());
+        assert!(imported.unknown_spells.is_empty());
+        assert_eq!(imported.report(), "Imported \"Bot lane\".");
+    }
+
+    #[test]
+    fn newer_versions_are_refused() {
+        let mut shared: serde_json::Value = serde_json::from_slice(
+            &base64::engine::general_purpose::STANDARD
+                .decode(export_string(&profile(), spell_key))
+                .unwrap(),
+        )
+        .unwrap();
+        shared["version"] = serde_json::json!(SHARE_VERSION + 1);
+
+        assert!(matches!(
+            import(&encode(shared), champion_name, spell_name),
+            Err(ImportError::Version(version)) if version == SHARE_VERSION + 1
+        ));
+        assert!(matches!(
+            import("not base64!", champion_name, spell_name),
+            Err(ImportError::Encoding(_))
+        ));
+    }
+
+    #[test]
+    fn unknown_champions_and_spells_are_left_out() {
+        let shared = serde_json::json!({
+            "version": SHARE_VERSION,
+            "name": "Unknown",
+            "auto_accept": false,
+            "pick_ban": true,
+            "picks": [22, 9999],
+            "bans": [8888, 1],
+            "spell_selection": true,
+            "spell1": 4,
+            "spell2": 77,
+            "lobby": crate::lobby::LobbySettings::default(),
+        });
+        let imported = import(&encode(shared), champion_name, spell_name).unwrap();
+
+        assert_eq!(imported.profile.picks, vec![(22, "Ashe".to_owned())]);
+        assert_eq!(imported.profile.bans, vec![(1, "Annie".to_owned())]);
+        assert_eq!(imported.profile.spell1.as_deref(), Some("Flash"));
+        assert_eq!(imported.profile.spell2, None);
+        assert_eq!(imported.unknown_champions, vec![9999, 8888]);
+        assert_eq!(imported.unknown_spells, vec![77]);
+        assert!(imported.report().contains("9999, 8888"));
+    }
+
+    #[test]
+    fn skipped_entries_are_kept() {
+        let skipping = Profile {
+            picks: vec![(0, String::new()), (22, "Ashe".to_owned())],
+            bans: vec![(0, String::new())],
+            ..profile()
+        };
+        let imported = import(
+            &export_string(&skipping, spell_key),
+            champion_name,
+            spell_name,
+        )
+        .unwrap();
+
+        assert_eq!(imported.profile.picks, skipping.picks);
+        assert_eq!(imported.profile.bans, skipping.bans);
+        assert!(imported.unknown_champions.is_empty());
+    }
+}
@@ -0,0 +1,376 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::io::Write;
+
+/// Directory the recorded champion selects and their CSV exports are written to.
+pub const DRAFTS_DIR: &str = "./utils/drafts";
+
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+/// The `Snapshot` struct is the state of a champion select at one point in time, one line of a draft file.
+///
+/// ### Properties:
+/// * `timestamp_ms`: When the snapshot was taken, in milliseconds since the Unix epoch.
+/// * `game_id`: The id of the game the champion select belongs to.
+/// * `queue_id`: The id of the queue.
+/// * `local_cell_id`: The cell of the player running the app.
+/// * `timer_phase`: The phase of the timer (`PLANNING`, `BAN_PICK`, `FINALIZATION`...).
+/// * `time_left_ms`: The raw `adjustedTimeLeftInPhase`, the time left when the LeagueClient last updated the timer,
+///   in milliseconds. Unlike [`crate::draft::time_left_ms`] it isn't corrected for the time since that update.
+/// * `actions`: The pick and ban actions as sent by the LeagueClient.
+/// * `my_team`: Our team as sent by the LeagueClient.
+/// * `their_team`: The enemy team as sent by the LeagueClient.
+pub struct Snapshot {
+    pub timestamp_ms: u64,
+    pub game_id: u64,
+    pub queue_id: u32,
+    pub local_cell_id: u64,
+    pub timer_phase: String,
+    pub time_left_ms: u64,
+    pub actions: serde_json::Value,
+    pub my_team: serde_json::Value,
+    pub their_team: serde_json::Value,
+}
+
+impl Snapshot {
+    pub fn from_session(session: &serde_json::Value, queue_id: u32) -> Self {
+        Self {
+            timestamp_ms: crate::draft::now_ms(),
+            game_id: session["gameId"].as_u64().unwrap_or(0),
+            queue_id,
+            local_cell_id: session["localPlayerCellId"].as_u64().unwrap_or(0),
+            timer_phase: session["timer"]["phase"]
+                .as_str()
+                .unwrap_or_default()
+                .to_owned(),
+            time_left_ms: session["timer"]["adjustedTimeLeftInPhase"]
+                .as_f64()
+                .unwrap_or(0.0)
+                .max(0.0) as u64,
+            actions: session["actions"].clone(),
+            my_team: session["myTeam"].clone(),
+            their_team: session["theirTeam"].clone(),
+        }
+    }
+
+    /// Rebuilds the parts of the session the snapshot was taken from.
+    pub fn to_session(&self) -> serde_json::Value {
+        serde_json::json!({
+            "gameId": self.game_id,
+            "localPlayerCellId": self.local_cell_id,
+            "timer": {
+                "phase": self.timer_phase,
+                "adjustedTimeLeftInPhase": self.time_left_ms
+            },
+            "actions": self.actions,
+            "myTeam": self.my_team,
+            "theirTeam": self.their_team
+        })
+    }
+
+    /// Whether anything but the clock differs between the two snapshots.
+    fn changed_from(&self, other: &Snapshot) -> bool {
+        self.timer_phase != other.timer_phase
+            || self.actions != other.actions
+            || self.my_team != other.my_team
+            || self.their_team != other.their_team
+    }
+}
+
+#[derive(Default)]
+/// The `DraftRecorder` struct writes a snapshot of the champion select to the draft file of the game whenever it changes.
+///
+/// ### Properties:
+/// * `path`: The draft file of the current champion select, `None` until the first snapshot.
+/// * `last`: The last snapshot written.
+pub struct DraftRecorder {
+    path: Option<String>,
+    last: Option<Snapshot>,
+}
+
+impl DraftRecorder {
+    /// Records the session if it changed since the last call.
+    pub fn record(&mut self, session: &serde_json::Value, queue_id: u32) -> std::io::Result<()> {
+        let snapshot = Snapshot::from_session(session, queue_id);
+        if matches!(&self.last, Some(last) if !snapshot.changed_from(last)) {
+            return Ok(());
+        }
+
+        let path = self.path.get_or_insert_with(|| {
+            format!(
+                "{}/{}-{}.jsonl",
+                DRAFTS_DIR,
+                snapshot.timestamp_ms / 1000,
+                snapshot.game_id
+            )
+        });
+        std::fs::create_dir_all(DRAFTS_DIR)?;
+        let mut file = std::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)?;
+        writeln!(file, "{}", serde_json::to_string(&snapshot)?)?;
+
+        self.last = Some(snapshot);
+        Ok(())
+    }
+
+    /// Ends the current champion select, the next snapshot starts a new draft file.
+    pub fn finish(&mut self) {
+        self.path = None;
+        self.last = None;
+    }
+}
+
+/// Quotes a CSV field if it contains a separator, a quote or a line break.
+fn csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\n']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_owned()
+    }
+}
+
+/// Reads every recorded draft, as the snapshots of each draft file in recording order.
+pub fn load_drafts(dir: &str) -> Vec<Vec<Snapshot>> {
+    let mut paths: Vec<std::path::PathBuf> = std::fs::read_dir(dir)
+        .into_iter()
+        .flatten()
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| path.extension() == Some(std::ffi::OsStr::new("jsonl")))
+        .collect();
+    paths.sort();
+
+    paths
+        .into_iter()
+        .map(|path| {
+            std::fs::read_to_string(path)
+                .unwrap_or_default()
+                .lines()
+                .filter_map(|line| serde_json::from_str(line).ok())
+                .collect::<Vec<Snapshot>>()
+        })
+        .filter(|snapshots| !snapshots.is_empty())
+        .collect()
+}
+
+/// Writes the CSV summaries of every recorded draft to `dir`, returning how many drafts were exported.
+///
+/// * `drafts.csv` has one row per draft with the final picks and bans of both teams.
+/// * `draft_actions.csv` has one row per completed pick or ban, in the order they were completed.
+pub fn export_csv(dir: &str, champion_name: impl Fn(u32) -> String) -> std::io::Result<usize> {
+    std::fs::create_dir_all(dir)?;
+    let drafts = load_drafts(dir);
+    let names = |champion_ids: &[u32]| {
+        champion_ids
+            .iter()
+            .map(|champion_id| champion_name(*champion_id))
+            .collect::<Vec<String>>()
+            .join(";")
+    };
+
+    let mut summary = std::fs::File::create(format!("{}/drafts.csv", dir))?;
+    writeln!(
+        summary,
+        "game_id,queue_id,started,snapshots,my_picks,their_picks,my_bans,their_bans"
+    )?;
+    let mut actions_file = std::fs::File::create(format!("{}/draft_actions.csv", dir))?;
+    writeln!(
+        actions_file,
+        "game_id,order,team,actor_cell_id,type,champion,completed_after_ms"
+    )?;
+
+    for snapshots in &drafts {
+        let first = &snapshots[0];
+        let last = &snapshots[snapshots.len() - 1];
+        let draft = crate::draft::Draft::from_session(&last.to_session(), last.timestamp_ms);
+        let picks = |team: &[crate::draft::Cell]| -> Vec<u32> {
+            team.iter()
+                .map(|cell| cell.champion_id)
+                .filter(|champion_id| *champion_id != 0)
+                .collect()
+        };
+
+        writeln!(
+            summary,
+            "{},{},{},{},{},{},{},{}",
+            last.game_id,
+            last.queue_id,
+            first.timestamp_ms / 1000,
+            snapshots.len(),
+            csv_field(&names(&picks(&draft.my_team))),
+            csv_field(&names(&picks(&draft.their_team))),
+            csv_field(&names(&draft.my_bans)),
+            csv_field(&names(&draft.their_bans)),
+        )?;
+
+        // Actions are identified by their id, the time they were completed is the first snapshot showing them completed
+        let allies: Vec<u64> = draft.my_team.iter().map(|cell| cell.cell_id).collect();
+        let mut completed: Vec<(u64, &serde_json::Value)> = Vec::new();
+        let mut seen: HashSet<u64> = HashSet::new();
+        for snapshot in snapshots {
+            let actions = snapshot
+                .actions
+                .as_array()
+                .into_iter()
+                .flatten()
+                .filter_map(|group| group.as_array())
+                .flatten();
+            for action in actions.filter(|action| action["completed"] == true) {
+                let id = action["id"].as_u64().unwrap_or(0);
+                if seen.insert(id) {
+                    completed.push((
+                        snapshot.timestamp_ms.saturating_sub(first.timestamp_ms),
+                        action,
+                    ));
+                }
+            }
+        }
+
+        for (order, (completed_after_ms, action)) in completed.into_iter().enumerate() {
+            let actor_cell_id = action["actorCellId"].as_u64().unwrap_or(0);
+            let champion_id = action["championId"].as_u64().unwrap_or(0) as u32;
+            writeln!(
+                actions_file,
+                "{},{},{},{},{},{},{}",
+                last.game_id,
+                order + 1,
+                if allies.contains(&actor_cell_id) {
+                    "ally"
+                } else {
+                    "enemy"
+                },
+                actor_cell_id,
+                csv_field(action["type"].as_str().unwrap_or_default()),
+                csv_field(&if champion_id == 0 {
+                    String::new()
+                } else {
+                    champion_name(champion_id)
+                }),
+                completed_after_ms,
+            )?;
+        }
+    }
+
+    Ok(drafts.len())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn snapshot(timestamp_ms: u64, actions: serde_json::Value, my_champion: u32) -> Snapshot {
+        Snapshot {
+            timestamp_ms,
+            game_id: 77,
+            queue_id: 420,
+            local_cell_id: 0,
+            timer_phase: "BAN_PICK".to_owned(),
+            time_left_ms: 30_000,
+            actions,
+            my_team: serde_json::json!([{ "cellId": 0, "championId": my_champion }]),
+            their_team: serde_json::json!([{ "cellId": 5, "championId": 0 }]),
+        }
+    }
+
+    fn action(
+        id: u64,
+        actor_cell_id: u64,
+        kind: &str,
+        champion_id: u32,
+        completed: bool,
+    ) -> serde_json::Value {
+        serde_json::json!({
+            "id": id,
+            "actorCellId": actor_cell_id,
+            "type": kind,
+            "championId": champion_id,
+            "completed": completed
+        })
+    }
+
+    #[test]
+    fn only_changes_besides_the_clock_are_recorded() {
+        let first = snapshot(
+            1000,
+            serde_json::json!([[action(1, 0, "ban", 1, false)]]),
+            0,
+        );
+        let ticked = Snapshot {
+            timestamp_ms: 2000,
+            time_left_ms: 29_000,
+            ..first.clone()
+        };
+        let banned = snapshot(3000, serde_json::json!([[action(1, 0, "ban", 1, true)]]), 0);
+        let next_phase = Snapshot {
+            timer_phase: "FINALIZATION".to_owned(),
+            ..first.clone()
+        };
+
+        assert!(!ticked.changed_from(&first));
+        assert!(banned.changed_from(&first));
+        assert!(next_phase.changed_from(&first));
+    }
+
+    #[test]
+    fn csv_fields_are_quoted_when_needed() {
+        assert_eq!(csv_field("Ashe"), "Ashe");
+        assert_eq!(csv_field(""), "");
+        assert_eq!(csv_field("Jarvan, IV"), "\"Jarvan, IV\"");
+        assert_eq!(csv_field("a \"b\""), "\"a \"\"b\"\"\"");
+        assert_eq!(csv_field("two\nlines"), "\"two\nlines\"");
+    }
+
+    #[test]
+    fn export_writes_a_row_per_draft_and_completed_action() {
+        let dir = std::env::temp_dir().join(format!("draft_export_test_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let snapshots = [
+            snapshot(
+                1_000_000,
+                serde_json::json!([[action(1, 0, "ban", 1, true), action(2, 5, "ban", 0, false)]]),
+                0,
+            ),
+            snapshot(
+                1_004_500,
+                serde_json::json!([
+                    [action(1, 0, "ban", 1, true), action(2, 5, "ban", 2, true)],
+                    [action(3, 0, "pick", 22, true)]
+                ]),
+                22,
+            ),
+        ];
+        let lines: Vec<String> = snapshots
+            .iter()
+            .map(|snapshot| serde_json::to_string(snapshot).unwrap())
+            .collect();
+        std::fs::write(dir.join("1000-77.jsonl"), lines.join("\n")).unwrap();
+
+        let champion_name = |champion_id: u32| {
+            match champion_id {
+                1 => "Annie",
+                2 => "Jarvan, IV",
+                22 => "Ashe",
+                _ => "",
+            }
+            .to_owned()
+        };
+        let exported = export_csv(dir.to_str().unwrap(), champion_name).unwrap();
+        let summary = std::fs::read_to_string(dir.join("drafts.csv")).unwrap();
+        let actions = std::fs::read_to_string(dir.join("draft_actions.csv")).unwrap();
+        std::fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(exported, 1);
+        assert_eq!(
+            summary.lines().skip(1).collect::<Vec<&str>>(),
+            vec!["77,420,1000,2,Ashe,,Annie,\"Jarvan, IV\""]
+        );
+        assert_eq!(
+            actions.lines().skip(1).collect::<Vec<&str>>(),
+            vec![
+                "77,1,ally,0,ban,Annie,0",
+                "77,2,enemy,5,ban,\"Jarvan, IV\",4500",
+                "77,3,ally,0,pick,Ashe,4500",
+            ]
+        );
+    }
+}
@@ -0,0 +1,248 @@
+use std::collections::HashMap;
+
+/// Lowercases the text and drops everything that isn't a letter or a digit,
+/// so "Kai'Sa", "kai sa" and "KAISA" all end up as "kaisa".
+pub fn normalize(text: &str) -> String {
+    text.chars()
+        .filter(|c| c.is_alphanumeric())
+        .flat_map(|c| c.to_lowercase())
+        .collect()
+}
+
+/// Optimal string alignment distance, the edit distance counting swapped neighbours as a single typo.
+fn typo_distance(a: &str, b: &str) -> usize {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+    let mut distances = vec![vec![0; b.len() + 1]; a.len() + 1];
+
+    for (i, row) in distances.iter_mut().enumerate() {
+        row[0] = i;
+    }
+    for (j, distance) in distances[0].iter_mut().enumerate() {
+        *distance = j;
+    }
+    for i in 1..=a.len() {
+        for j in 1..=b.len() {
+            let cost = usize::from(a[i - 1] != b[j - 1]);
+            distances[i][j] = (distances[i - 1][j] + 1)
+                .min(distances[i][j - 1] + 1)
+                .min(distances[i - 1][j - 1] + cost);
+            if i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+                distances[i][j] = distances[i][j].min(distances[i - 2][j - 2] + 1);
+            }
+        }
+    }
+
+    distances[a.len()][b.len()]
+}
+
+/// How many typos a query of the given length may contain and still match.
+fn allowed_typos(query_length: usize) -> usize {
+    match query_length {
+        0..=2 => 0,
+        3..=5 => 1,
+        _ => 2,
+    }
+}
+
+/// The `ChampionSearch` struct finds champions by (partial, misspelled or aliased) name.
+///
+/// ### Properties:
+/// * `champions`: The champions as `(id, name, normalized name)`.
+/// * `aliases`: Normalized nicknames mapped to the id of the champion they refer to.
+pub struct ChampionSearch {
+    champions: Vec<(u32, String, String)>,
+    aliases: HashMap<String, u32>,
+}
+
+impl ChampionSearch {
+    /// Builds the search from `(id, name)` pairs and an alias table mapping nicknames to champion names.
+    /// Aliases pointing to unknown champion names are ignored.
+    pub fn new<'a>(
+        champions: impl IntoIterator<Item = (u32, &'a str)>,
+        aliases: &HashMap<String, String>,
+    ) -> Self {
+        let champions: Vec<(u32, String, String)> = champions
+            .into_iter()
+            .map(|(id, name)| (id, name.to_owned(), normalize(name)))
+            .collect();
+
+        let aliases = aliases
+            .iter()
+            .filter_map(|(alias, name)| {
+                let name = normalize(name);
+                champions
+                    .iter()
+                    .find(|(_, _, normalized)| *normalized == name)
+                    .map(|(id, _, _)| (normalize(alias), *id))
+            })
+            .collect();
+
+        Self { champions, aliases }
+    }
+
+    /// Reads the alias table from a JSON object of `"alias": "Champion Name"` pairs,
+    /// an unreadable file just means there are no aliases.
+    pub fn load_aliases(path: &str) -> HashMap<String, String> {
+        std::fs::read_to_string(path)
+            .ok()
+            .and_then(|data| serde_json::from_str(&data).ok())
+            .unwrap_or_default()
+    }
+
+    /// Returns the name of the champion with the given id.
+    pub fn name(&self, champion_id: u32) -> Option<&str> {
+        self.champions
+            .iter()
+            .find(|(id, _, _)| *id == champion_id)
+            .map(|(_, name, _)| name.as_str())
+    }
+
+    /// Finds the champion the text refers to exactly, either by name or by alias.
+    pub fn find(&self, text: &str) -> Option<(u32, String)> {
+        let query = normalize(text);
+        if query.is_empty() {
+            return None;
+        }
+
+        let id = self
+            .champions
+            .iter()
+            .find(|(_, _, normalized)| *normalized == query)
+            .map(|(id, _, _)| *id)
+            .or_else(|| self.aliases.get(&query).copied())?;
+
+        self.champions
+            .iter()
+            .find(|(champion_id, _, _)| *champion_id == id)
+            .map(|(id, name, _)| (*id, name.clone()))
+    }
+
+    /// Ranks every champion matching the text, best match first: exact names and aliases, then names
+    /// starting with the text, aliases starting with it, names containing it and finally names with typos.
+    pub fn search(&self, text: &str) -> Vec<(u32, String)> {
+        let query = normalize(text);
+        if query.is_empty() {
+            return Vec::new();
+        }
+        let aliases_of = |id: u32| {
+            self.aliases
+                .iter()
+                .filter(move |(_, alias_id)| **alias_id == id)
+                .map(|(alias, _)| alias)
+        };
+
+        let mut ranked: Vec<(usize, &(u32, String, String))> = self
+            .champions
+            .iter()
+            .filter_map(|champion| {
+                let (id, _, normalized) = champion;
+
+                let rank = if *normalized == query || aliases_of(*id).any(|alias| *alias == query) {
+                    0
+                } else if normalized.starts_with(&query) {
+                    1
+                } else if aliases_of(*id).any(|alias| alias.starts_with(&query)) {
+                    2
+                } else if normalized.contains(&query) {
+                    3
+                } else {
+                    // Typos are compared against the whole name and against its start, for partially typed names
+                    let prefix: String = normalized.chars().take(query.chars().count()).collect();
+                    let distance =
+                        typo_distance(&query, normalized).min(typo_distance(&query, &prefix));
+                    if distance > allowed_typos(query.chars().count()) {
+                        return None;
+                    }
+                    3 + distance
+                };
+
+                Some((rank, champion))
+            })
+            .collect();
+
+        ranked.sort_by(|(rank_a, (_, name_a, _)), (rank_b, (_, name_b, _))| {
+            rank_a.cmp(rank_b).then_with(|| name_a.cmp(name_b))
+        });
+        ranked
+            .into_iter()
+            .map(|(_, (id, name, _))| (*id, name.clone()))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn champion_search() -> ChampionSearch {
+        let champions = [
+            (145, "Kai'Sa"),
+            (64, "Lee Sin"),
+            (21, "Miss Fortune"),
+            (4, "Twisted Fate"),
+            (136, "Aurelion Sol"),
+            (157, "Yasuo"),
+            (777, "Yone"),
+            (36, "Dr. Mundo"),
+            (202, "Jhin"),
+        ];
+        let aliases = HashMap::from([
+            ("mf".to_owned(), "Miss Fortune".to_owned()),
+            ("tf".to_owned(), "Twisted Fate".to_owned()),
+            ("asol".to_owned(), "Aurelion Sol".to_owned()),
+            ("nobody".to_owned(), "Not A Champion".to_owned()),
+        ]);
+
+        ChampionSearch::new(champions, &aliases)
+    }
+
+    #[test]
+    fn normalize_strips_punctuation_and_case() {
+        assert_eq!(normalize("Kai'Sa"), "kaisa");
+        assert_eq!(normalize(" Lee Sin "), "leesin");
+        assert_eq!(normalize("Dr. Mundo"), "drmundo");
+    }
+
+    #[test]
+    fn find_matches_names_with_spaces_and_apostrophes() {
+        let search = champion_search();
+
+        assert_eq!(search.find("Kai'Sa"), Some((145, "Kai'Sa".to_owned())));
+        assert_eq!(search.find("kaisa"), Some((145, "Kai'Sa".to_owned())));
+        assert_eq!(search.find("Lee Sin"), Some((64, "Lee Sin".to_owned())));
+        assert_eq!(search.find("dr mundo"), Some((36, "Dr. Mundo".to_owned())));
+        assert_eq!(search.find("lee"), None);
+        assert_eq!(search.find(""), None);
+    }
+
+    #[test]
+    fn find_resolves_aliases() {
+        let search = champion_search();
+
+        assert_eq!(search.find("MF"), Some((21, "Miss Fortune".to_owned())));
+        assert_eq!(search.find("tf"), Some((4, "Twisted Fate".to_owned())));
+        assert_eq!(search.find("asol"), Some((136, "Aurelion Sol".to_owned())));
+        assert_eq!(search.find("nobody"), None);
+    }
+
+    #[test]
+    fn search_ranks_prefixes_before_typos() {
+        let search = champion_search();
+        let results: Vec<u32> = search.search("y").into_iter().map(|(id, _)| id).collect();
+
+        assert_eq!(results, vec![157, 777]);
+        assert_eq!(search.search("as")[0].0, 136);
+        assert_eq!(search.search("fortune")[0].0, 21);
+    }
+
+    #[test]
+    fn search_tolerates_typos() {
+        let search = champion_search();
+
+        assert_eq!(search.search("yasou")[0].0, 157);
+        assert_eq!(search.search("jihn")[0].0, 202);
+        assert_eq!(search.search("kasia")[0].0, 145);
+        assert!(search.search("xyzw").is_empty());
+    }
+}
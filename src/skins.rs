@@ -0,0 +1,146 @@
+use serde::{Deserialize, Serialize};
+
+#[allow(non_snake_case)]
+#[derive(Debug, Deserialize, Clone)]
+/// The `Skin` struct is used for deserializing the skins of a champion from the LeagueClient inventory.
+///
+/// ### Properties:
+/// * `id`: The skin id, also used as `selectedSkinId` in the champion selection.
+/// * `name`: The name of the skin.
+/// * `isBase`: Whether this is the default skin of the champion.
+/// * `ownership`: Whether the player owns the skin.
+/// * `chromas`: The chromas of the skin, empty if it has none.
+pub struct Skin {
+    pub id: u32,
+    pub name: String,
+    #[serde(default)]
+    pub isBase: bool,
+    pub ownership: Ownership,
+    #[serde(default)]
+    pub chromas: Vec<Chroma>,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct Chroma {
+    pub id: u32,
+    pub ownership: Ownership,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct Ownership {
+    #[serde(default)]
+    pub owned: bool,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq)]
+/// The skin to pick for a champion once it's locked. Champions without a choice keep the client's default.
+pub enum SkinChoice {
+    /// A specific skin or chroma id.
+    Skin(u32),
+    /// Any owned skin other than the base skin.
+    Random,
+}
+
+/// Lists the skins and chromas the player owns, as `(id, name)` pairs. Chromas are listed right after their skin.
+pub fn owned_skins(skins: &[Skin]) -> Vec<(u32, String)> {
+    let mut owned = Vec::new();
+
+    for skin in skins.iter().filter(|skin| skin.ownership.owned) {
+        owned.push((skin.id, skin.name.clone()));
+        for (idx, chroma) in skin
+            .chromas
+            .iter()
+            .filter(|chroma| chroma.ownership.owned)
+            .enumerate()
+        {
+            owned.push((chroma.id, format!("{} (Chroma {})", skin.name, idx + 1)));
+        }
+    }
+
+    owned
+}
+
+/// Resolves a skin choice into the skin id to select, `None` if the chosen skin isn't owned
+/// or there is no owned skin to pick randomly from.
+pub fn choose_skin(choice: SkinChoice, skins: &[Skin]) -> Option<u32> {
+    match choice {
+        SkinChoice::Skin(skin_id) => owned_skins(skins)
+            .iter()
+            .any(|(id, _)| *id == skin_id)
+            .then_some(skin_id),
+        SkinChoice::Random => {
+            let candidates: Vec<u32> = skins
+                .iter()
+                .filter(|skin| skin.ownership.owned && !skin.isBase)
+                .map(|skin| skin.id)
+                .collect();
+            if candidates.is_empty() {
+                return None;
+            }
+
+            // Doesn't need to be a good random number, just a different skin every now and then
+            let seed = std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .map(|duration| duration.subsec_nanos() as usize)
+                .unwrap_or(0);
+            Some(candidates[seed % candidates.len()])
+        }
+    }
+}
+
+/// Checks if the local player has already locked their champion.
+/// Champions in modes with a bench (ARAM) count as locked right away since there are no pick actions.
+pub fn pick_locked(champ_select: &serde_json::Value) -> bool {
+    if champ_select["benchEnabled"] == true {
+        return true;
+    }
+
+    champ_select["actions"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .flat_map(|turn| turn.as_array().into_iter().flatten())
+        .any(|action| {
+            action["actorCellId"] == champ_select["localPlayerCellId"]
+                && action["type"] == "pick"
+                && action["completed"] == true
+        })
+}
+
+/// Fetches every skin of a champion from the inventory of the current summoner.
+///
+/// ### Arguments:
+/// * `lcu`: The client the LeagueClient requests go through.
+/// * `champion_id`: The id of the champion.
+pub async fn fetch_skins(
+    lcu: &crate::lcu::LcuClient,
+    champion_id: u32,
+) -> Result<Vec<Skin>, crate::lcu::Error> {
+    let summoner = lcu.get("/lol-summoner/v1/current-summoner").await?;
+
+    let skins = lcu
+        .get(&format!(
+            "/lol-champions/v1/inventories/{}/champions/{}/skins",
+            summoner["summonerId"], champion_id
+        ))
+        .await?;
+    Ok(serde_json::from_value(skins)?)
+}
+
+/// Selects a skin (or chroma) for the locked champion.
+///
+/// ### Arguments:
+/// * `lcu`: The client the LeagueClient requests go through.
+/// * `skin_id`: The id of the skin or chroma.
+pub async fn select_skin(
+    lcu: &crate::lcu::LcuClient,
+    skin_id: u32,
+) -> Result<(), crate::lcu::Error> {
+    lcu.patch(
+        "/lol-champ-select/v1/session/my-selection",
+        &serde_json::json!({ "selectedSkinId": skin_id }),
+    )
+    .await?;
+
+    Ok(())
+}
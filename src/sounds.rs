@@ -0,0 +1,163 @@
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::sync::{mpsc, Arc, Mutex};
+
+/// File the sound settings are saved to.
+pub const SOUNDS_PATH: &str = "./utils/sounds.json";
+
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq, Eq, Hash)]
+/// Moments a sound can be played at.
+pub enum Cue {
+    /// A match was found and the ready check started.
+    ReadyCheck,
+    /// One of the pick or ban actions of the local player is in progress.
+    Turn,
+    /// The champion of the local player got locked in.
+    Lock,
+}
+
+impl Cue {
+    pub const ALL: [Cue; 3] = [Cue::ReadyCheck, Cue::Turn, Cue::Lock];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Cue::ReadyCheck => "Match Found",
+            Cue::Turn => "Your Pick/Ban Turn",
+            Cue::Lock => "Champion Locked",
+        }
+    }
+
+    /// The sound bundled with the app, used when no file is set for the cue.
+    fn bundled(&self) -> &'static [u8] {
+        match self {
+            Cue::ReadyCheck => include_bytes!("../utils/sounds/ready_check.wav"),
+            Cue::Turn => include_bytes!("../utils/sounds/turn.wav"),
+            Cue::Lock => include_bytes!("../utils/sounds/lock.wav"),
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+#[serde(default)]
+/// The `SoundSettings` struct holds which cues play a sound and how.
+///
+/// ### Properties:
+/// * `enabled`: The cues a sound is played for, none by default.
+/// * `volume`: The volume of every sound, from `0.0` to `1.0`.
+/// * `files`: WAV or OGG files replacing the bundled sound of a cue.
+pub struct SoundSettings {
+    pub enabled: HashSet<Cue>,
+    pub volume: f32,
+    pub files: HashMap<Cue, String>,
+}
+
+impl Default for SoundSettings {
+    fn default() -> Self {
+        Self {
+            enabled: HashSet::new(),
+            volume: 0.5,
+            files: HashMap::new(),
+        }
+    }
+}
+
+impl SoundSettings {
+    /// Reads the sound settings file, the default settings if it doesn't exist or can't be parsed.
+    pub fn load(path: &str) -> Self {
+        std::fs::read_to_string(path)
+            .ok()
+            .and_then(|data| serde_json::from_str(&data).ok())
+            .unwrap_or_default()
+    }
+
+    pub fn save(&self, path: &str) -> std::io::Result<()> {
+        std::fs::write(path, serde_json::to_string_pretty(self)?)
+    }
+}
+
+#[derive(Clone)]
+/// The `SoundPlayer` struct plays the sound cues from a thread of its own, which keeps the audio output open.
+///
+/// ### Properties:
+/// * `settings`: The sound settings, shared with the GUI.
+/// * `error`: Why the last sound couldn't be played as set, shown in the settings.
+/// * `sender`: Sends the cues to play to the audio thread.
+pub struct SoundPlayer {
+    pub settings: Arc<Mutex<SoundSettings>>,
+    pub error: Arc<Mutex<Option<String>>>,
+    sender: mpsc::Sender<Cue>,
+}
+
+impl SoundPlayer {
+    pub fn start(settings: SoundSettings) -> Self {
+        let settings = Arc::new(Mutex::new(settings));
+        let error = Arc::new(Mutex::new(None));
+        let (sender, receiver) = mpsc::channel::<Cue>();
+
+        let settings_clone = Arc::clone(&settings);
+        let error_clone = Arc::clone(&error);
+        std::thread::spawn(move || {
+            // Dropping the stream closes the audio output, so it lives as long as the thread
+            let Ok((_stream, handle)) = rodio::OutputStream::try_default() else {
+                *error_clone.lock().unwrap() = Some("No audio output device found".to_owned());
+                return;
+            };
+
+            for cue in receiver {
+                let (volume, file) = {
+                    let settings = settings_clone.lock().unwrap();
+                    (settings.volume, settings.files.get(&cue).cloned())
+                };
+                // Cleared once a sound plays as set, so only the last problem is shown
+                let mut problem = None;
+                let bytes = match file.filter(|path| !path.trim().is_empty()) {
+                    Some(path) => match std::fs::read(path.trim()) {
+                        Ok(bytes) => bytes,
+                        Err(error) => {
+                            problem = Some(format!("Couldn't read {}: {}", path.trim(), error));
+                            cue.bundled().to_vec()
+                        }
+                    },
+                    None => cue.bundled().to_vec(),
+                };
+
+                let played = rodio::Decoder::new(std::io::Cursor::new(bytes))
+                    .map_err(|error| error.to_string())
+                    .and_then(|source| {
+                        let sink =
+                            rodio::Sink::try_new(&handle).map_err(|error| error.to_string())?;
+                        sink.set_volume(volume);
+                        sink.append(source);
+                        sink.detach();
+                        Ok(())
+                    });
+                if let Err(error) = played {
+                    problem = Some(format!(
+                        "Couldn't play the {} sound: {}",
+                        cue.label(),
+                        error
+                    ));
+                }
+                *error_clone.lock().unwrap() = problem;
+            }
+        });
+
+        Self {
+            settings,
+            error,
+            sender,
+        }
+    }
+
+    /// Plays the sound of a cue if it's turned on.
+    pub fn play(&self, cue: Cue) {
+        if self.settings.lock().unwrap().enabled.contains(&cue) {
+            self.preview(cue);
+        }
+    }
+
+    /// Plays the sound of a cue even if it's turned off, to try it from the settings.
+    pub fn preview(&self, cue: Cue) {
+        let _ = self.sender.send(cue);
+    }
+}
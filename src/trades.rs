@@ -0,0 +1,149 @@
+/// Kinds of champion select requests other players can send us, named after their endpoint.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum RequestKind {
+    Trade,
+    PickOrderSwap,
+}
+
+impl RequestKind {
+    /// The key of the request list in the champion select session.
+    pub fn session_key(&self) -> &'static str {
+        match self {
+            RequestKind::Trade => "trades",
+            RequestKind::PickOrderSwap => "pickOrderSwaps",
+        }
+    }
+
+    fn endpoint(&self) -> &'static str {
+        match self {
+            RequestKind::Trade => "trades",
+            RequestKind::PickOrderSwap => "pick-order-swaps",
+        }
+    }
+}
+
+/// Reads the requests we received and haven't answered yet, as `(id, cellId)` pairs.
+pub fn received_requests(champ_select: &serde_json::Value, kind: RequestKind) -> Vec<(u64, u64)> {
+    champ_select[kind.session_key()]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter(|request| request["state"] == "RECEIVED")
+        .filter_map(|request| Some((request["id"].as_u64()?, request["cellId"].as_u64()?)))
+        .collect()
+}
+
+/// Returns the position of the first pick action of a cell, lower picks earlier.
+fn pick_turn(champ_select: &serde_json::Value, cell_id: u64) -> Option<usize> {
+    champ_select["actions"]
+        .as_array()?
+        .iter()
+        .flat_map(|turn| turn.as_array().into_iter().flatten())
+        .filter(|action| action["type"] == "pick")
+        .position(|action| action["actorCellId"] == cell_id)
+}
+
+/// A pick order swap is only accepted if it moves us to an earlier pick.
+pub fn accept_pick_order_swap(
+    champ_select: &serde_json::Value,
+    local_cell_id: u64,
+    other_cell_id: u64,
+) -> bool {
+    match (
+        pick_turn(champ_select, local_cell_id),
+        pick_turn(champ_select, other_cell_id),
+    ) {
+        (Some(our_turn), Some(their_turn)) => their_turn < our_turn,
+        _ => false,
+    }
+}
+
+/// A trade is only accepted if it gives us a champion higher on our priority list than the current one.
+/// Champions missing from the list rank below every champion in it.
+///
+/// ### Arguments:
+/// * `priority`: Champion ids in priority order, highest first.
+/// * `our_champion`: The champion id we currently have.
+/// * `their_champion`: The champion id we'd get from the trade.
+pub fn accept_trade(priority: &[u32], our_champion: u32, their_champion: u32) -> bool {
+    let rank = |champion_id: u32| {
+        priority
+            .iter()
+            .position(|id| *id == champion_id)
+            .unwrap_or(usize::MAX)
+    };
+
+    rank(their_champion) < rank(our_champion)
+}
+
+/// Accepts or declines a request we received.
+///
+/// ### Arguments:
+/// * `lcu`: The client the LeagueClient requests go through.
+/// * `kind`: Whether the request is a trade or a pick order swap.
+/// * `id`: The id of the request.
+/// * `accept`: Whether the request is accepted or declined.
+pub async fn respond(
+    lcu: &crate::lcu::LcuClient,
+    kind: RequestKind,
+    id: u64,
+    accept: bool,
+) -> Result<(), crate::lcu::Error> {
+    let response = if accept { "accept" } else { "decline" };
+
+    lcu.post(
+        &format!(
+            "/lol-champ-select/v1/session/{}/{}/{}",
+            kind.endpoint(),
+            id,
+            response
+        ),
+        None,
+    )
+    .await?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn session() -> serde_json::Value {
+        serde_json::json!({
+            "actions": [
+                [{ "type": "ban", "actorCellId": 0 }, { "type": "ban", "actorCellId": 1 }],
+                [{ "type": "pick", "actorCellId": 1 }],
+                [{ "type": "pick", "actorCellId": 0 }, { "type": "pick", "actorCellId": 2 }]
+            ]
+        })
+    }
+
+    #[test]
+    fn trades_need_a_higher_priority_champion() {
+        assert!(accept_trade(&[22, 51], 51, 22));
+        assert!(accept_trade(&[22, 51], 1, 51));
+        assert!(!accept_trade(&[22, 51], 22, 51));
+        assert!(!accept_trade(&[22, 51], 22, 1));
+    }
+
+    #[test]
+    fn trades_between_champions_missing_from_the_list_are_declined() {
+        assert!(!accept_trade(&[22, 51], 1, 2));
+        assert!(!accept_trade(&[], 1, 2));
+    }
+
+    #[test]
+    fn pick_order_swaps_need_an_earlier_pick() {
+        assert!(accept_pick_order_swap(&session(), 0, 1));
+        assert!(!accept_pick_order_swap(&session(), 0, 2));
+        assert!(!accept_pick_order_swap(&session(), 1, 0));
+    }
+
+    #[test]
+    fn pick_order_swaps_with_unknown_cells_are_declined() {
+        assert!(!accept_pick_order_swap(&session(), 0, 9));
+        assert!(!accept_pick_order_swap(&session(), 9, 1));
+        assert!(!accept_pick_order_swap(&serde_json::Value::Null, 0, 1));
+    }
+}
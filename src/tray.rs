@@ -0,0 +1,295 @@
+use std::cell::RefCell;
+use std::collections::VecDeque;
+use std::ptr;
+use std::sync::{
+    atomic::{AtomicBool, AtomicIsize, Ordering},
+    Arc, Mutex,
+};
+use winapi::shared::windef::{HWND, POINT};
+use winapi::um::libloaderapi::GetModuleHandleW;
+use winapi::um::shellapi::{
+    Shell_NotifyIconW, NIF_ICON, NIF_INFO, NIF_MESSAGE, NIF_TIP, NIIF_INFO, NIM_ADD, NIM_DELETE,
+    NIM_MODIFY, NOTIFYICONDATAW,
+};
+use winapi::um::winuser::{
+    AppendMenuW, CreateIconFromResourceEx, CreatePopupMenu, CreateWindowExW, DefWindowProcW,
+    DestroyMenu, DispatchMessageW, FindWindowW, GetCursorPos, IsIconic, IsWindow, IsWindowVisible,
+    PeekMessageW, PostMessageW, RegisterClassW, SetForegroundWindow, ShowWindow, TrackPopupMenu,
+    LR_DEFAULTCOLOR, MF_CHECKED, MF_SEPARATOR, MF_STRING, MSG, PM_REMOVE, SW_HIDE, SW_RESTORE,
+    SW_SHOW, TPM_NONOTIFY, TPM_RETURNCMD, TPM_RIGHTBUTTON, WM_APP, WM_LBUTTONUP, WM_NULL,
+    WM_RBUTTONUP, WNDCLASSW,
+};
+
+/// Title of the main window, also shown in the tooltip of the icon.
+pub const WINDOW_TITLE: &str = "Circuit Watcher";
+/// Class of the hidden window receiving the tray icon messages.
+const CLASS_NAME: &str = "CircuitWatcherTray";
+/// Message the tray icon sends on clicks.
+const TRAY_MESSAGE: u32 = WM_APP + 1;
+
+const ID_OPEN: usize = 1;
+const ID_QUIT: usize = 2;
+/// Ids of the toggles in the menu, in the order of [`Tray::toggles`].
+const ID_TOGGLES: usize = 10;
+
+#[derive(Clone)]
+/// The `Tray` struct holds what the tray icon shows and toggles, shared with the GUI and the worker.
+///
+/// ### Properties:
+/// * `auto_accept`: Toggled from the menu like the checkbox of the GUI.
+/// * `pick_ban_selection`: Toggled from the menu like the checkbox of the GUI.
+/// * `paused`: Toggled from the menu like the checkbox of the GUI.
+/// * `minimize_to_tray`: Whether minimizing the window hides it, it's shown again from the tray icon.
+/// * `main_window`: The handle of the main window as given by eframe, `0` until the first frame is drawn.
+/// * `gameflow_status`: The status of the worker, shown in the tooltip of the icon.
+/// * `notifications`: Desktop notifications to show from the icon, as `(title, message)` pairs.
+pub struct Tray {
+    pub auto_accept: Arc<AtomicBool>,
+    pub pick_ban_selection: Arc<AtomicBool>,
+    pub paused: Arc<AtomicBool>,
+    pub minimize_to_tray: Arc<AtomicBool>,
+    pub main_window: Arc<AtomicIsize>,
+    pub gameflow_status: Arc<Mutex<String>>,
+    pub notifications: Arc<Mutex<VecDeque<(String, String)>>>,
+}
+
+impl Tray {
+    fn toggles(&self) -> [(&str, &AtomicBool); 4] {
+        [
+            ("Auto Accept", &self.auto_accept),
+            ("Auto-Pick/Ban", &self.pick_ban_selection),
+            ("Pause All Automation", &self.paused),
+            ("Minimize to Tray", &self.minimize_to_tray),
+        ]
+    }
+
+    /// The main window, null until eframe gave its handle or once it's closed.
+    fn main_window(&self) -> HWND {
+        let window = self.main_window.load(Ordering::SeqCst) as HWND;
+        if !window.is_null() && unsafe { IsWindow(window) } != 0 {
+            window
+        } else {
+            ptr::null_mut()
+        }
+    }
+
+    /// Shows the main window again, restoring it if it was minimized.
+    fn show_main_window(&self) {
+        let window = self.main_window();
+        if !window.is_null() {
+            unsafe {
+                ShowWindow(window, SW_SHOW);
+                ShowWindow(window, SW_RESTORE);
+                SetForegroundWindow(window);
+            }
+        }
+    }
+
+    fn tooltip(&self) -> String {
+        let status = self.gameflow_status.lock().unwrap().clone();
+        if status.is_empty() {
+            WINDOW_TITLE.to_owned()
+        } else {
+            format!("{}\n{}", WINDOW_TITLE, status)
+        }
+    }
+}
+
+/// Null terminated UTF-16 string for the Windows API.
+fn wide(text: &str) -> Vec<u16> {
+    text.encode_utf16().chain(std::iter::once(0)).collect()
+}
+
+/// Copies a string into a fixed size buffer of `NOTIFYICONDATAW`, cut to fit.
+fn copy_wide(buffer: &mut [u16], text: &str) {
+    let units = text
+        .encode_utf16()
+        .take(buffer.len() - 1)
+        .chain(std::iter::repeat(0));
+    for (slot, unit) in buffer.iter_mut().zip(units) {
+        *slot = unit;
+    }
+}
+
+thread_local! {
+    /// The tray handled by the thread, for the window procedure of its hidden window.
+    static TRAY: RefCell<Option<Tray>> = const { RefCell::new(None) };
+}
+
+/// Window procedure of the hidden window, the icon sends its clicks to it.
+unsafe extern "system" fn window_proc(
+    window: HWND,
+    message: u32,
+    wparam: usize,
+    lparam: isize,
+) -> isize {
+    if message != TRAY_MESSAGE {
+        return DefWindowProcW(window, message, wparam, lparam);
+    }
+    TRAY.with(|tray| {
+        if let Some(tray) = tray.borrow().as_ref() {
+            match lparam as u32 {
+                WM_LBUTTONUP => tray.show_main_window(),
+                WM_RBUTTONUP => show_menu(window, tray),
+                _ => {}
+            }
+        }
+    });
+    0
+}
+
+/// Removes the tray icon, otherwise it stays in the tray until hovered once the app is closed.
+pub fn remove_icon() {
+    unsafe {
+        let window = FindWindowW(wide(CLASS_NAME).as_ptr(), ptr::null());
+        if window.is_null() {
+            return;
+        }
+        let mut data: NOTIFYICONDATAW = std::mem::zeroed();
+        data.cbSize = std::mem::size_of::<NOTIFYICONDATAW>() as u32;
+        data.hWnd = window;
+        data.uID = 1;
+        Shell_NotifyIconW(NIM_DELETE, &mut data);
+    }
+}
+
+/// Adds the tray icon and handles it from a thread of its own: a left click shows the window,
+/// a right click opens the menu with the toggles. The tooltip follows the status of the worker
+/// and queued notifications are shown as desktop notifications of the icon.
+pub fn run(tray: Tray) {
+    std::thread::spawn(move || unsafe {
+        let instance = GetModuleHandleW(ptr::null());
+        let class_name = wide(CLASS_NAME);
+        let mut class: WNDCLASSW = std::mem::zeroed();
+        TRAY.with(|current| *current.borrow_mut() = Some(tray.clone()));
+        class.lpfnWndProc = Some(window_proc);
+        class.hInstance = instance;
+        class.lpszClassName = class_name.as_ptr();
+        RegisterClassW(&class);
+        // Never shown, it only receives the messages of the icon
+        let window = CreateWindowExW(
+            0,
+            class_name.as_ptr(),
+            class_name.as_ptr(),
+            0,
+            0,
+            0,
+            0,
+            0,
+            ptr::null_mut(),
+            ptr::null_mut(),
+            instance,
+            ptr::null_mut(),
+        );
+
+        let mut icon = include_bytes!("../utils/images/icon.png").to_vec();
+        let mut data: NOTIFYICONDATAW = std::mem::zeroed();
+        data.cbSize = std::mem::size_of::<NOTIFYICONDATAW>() as u32;
+        data.hWnd = window;
+        data.uID = 1;
+        data.uFlags = NIF_ICON | NIF_MESSAGE | NIF_TIP;
+        data.uCallbackMessage = TRAY_MESSAGE;
+        data.hIcon = CreateIconFromResourceEx(
+            icon.as_mut_ptr(),
+            icon.len() as u32,
+            1,
+            0x00030000,
+            0,
+            0,
+            LR_DEFAULTCOLOR,
+        );
+        let mut tooltip = tray.tooltip();
+        copy_wide(&mut data.szTip, &tooltip);
+        Shell_NotifyIconW(NIM_ADD, &mut data);
+
+        loop {
+            let current_tooltip = tray.tooltip();
+            if current_tooltip != tooltip {
+                tooltip = current_tooltip;
+                copy_wide(&mut data.szTip, &tooltip);
+                Shell_NotifyIconW(NIM_MODIFY, &mut data);
+            }
+
+            let notification = tray.notifications.lock().unwrap().pop_front();
+            if let Some((title, message)) = notification {
+                copy_wide(&mut data.szInfoTitle, &title);
+                copy_wide(&mut data.szInfo, &message);
+                data.dwInfoFlags = NIIF_INFO;
+                data.uFlags |= NIF_INFO;
+                Shell_NotifyIconW(NIM_MODIFY, &mut data);
+                // Otherwise the notification would show again on the next tooltip change
+                data.uFlags &= !NIF_INFO;
+            }
+
+            if tray.minimize_to_tray.load(Ordering::SeqCst) {
+                let main_window = tray.main_window();
+                if !main_window.is_null()
+                    && IsWindowVisible(main_window) != 0
+                    && IsIconic(main_window) != 0
+                {
+                    ShowWindow(main_window, SW_HIDE);
+                }
+            }
+
+            // Posted messages are handed to the window procedure here, sent ones get there on their own
+            let mut message: MSG = std::mem::zeroed();
+            while PeekMessageW(&mut message, window, 0, 0, PM_REMOVE) != 0 {
+                DispatchMessageW(&message);
+            }
+            std::thread::sleep(std::time::Duration::from_millis(50));
+        }
+    });
+}
+
+unsafe fn show_menu(window: HWND, tray: &Tray) {
+    let menu = CreatePopupMenu();
+    AppendMenuW(
+        menu,
+        MF_STRING,
+        ID_OPEN,
+        wide("Open Circuit Watcher").as_ptr(),
+    );
+    AppendMenuW(menu, MF_SEPARATOR, 0, ptr::null());
+    for (idx, (label, setting)) in tray.toggles().iter().enumerate() {
+        let flags = if setting.load(Ordering::SeqCst) {
+            MF_STRING | MF_CHECKED
+        } else {
+            MF_STRING
+        };
+        AppendMenuW(menu, flags, ID_TOGGLES + idx, wide(label).as_ptr());
+    }
+    AppendMenuW(menu, MF_SEPARATOR, 0, ptr::null());
+    AppendMenuW(menu, MF_STRING, ID_QUIT, wide("Quit").as_ptr());
+
+    let mut position = POINT { x: 0, y: 0 };
+    GetCursorPos(&mut position);
+    // The menu only closes when clicking elsewhere if its window is in the foreground
+    SetForegroundWindow(window);
+    let command = TrackPopupMenu(
+        menu,
+        TPM_RETURNCMD | TPM_NONOTIFY | TPM_RIGHTBUTTON,
+        position.x,
+        position.y,
+        0,
+        window,
+        ptr::null(),
+    ) as usize;
+    PostMessageW(window, WM_NULL, 0, 0);
+    DestroyMenu(menu);
+
+    match command {
+        ID_OPEN => tray.show_main_window(),
+        ID_QUIT => {
+            remove_icon();
+            std::process::exit(0);
+        }
+        _ => {
+            if let Some((_, setting)) = command
+                .checked_sub(ID_TOGGLES)
+                .and_then(|idx| tray.toggles().get(idx).copied())
+            {
+                setting.fetch_xor(true, Ordering::SeqCst);
+            }
+        }
+    }
+}
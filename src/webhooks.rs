@@ -0,0 +1,462 @@
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// File the webhook settings are saved to.
+pub const WEBHOOKS_PATH: &str = "./utils/webhooks.json";
+/// Deliveries are given up after this many failed attempts.
+const MAX_ATTEMPTS: u32 = 5;
+/// Longest wait a server can ask for before a retry, longer waits are cut to it.
+const MAX_RETRY_AFTER: Duration = Duration::from_secs(300);
+
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq, Eq, Hash)]
+/// Events a webhook can be posted for.
+pub enum WebhookEvent {
+    MatchAccepted,
+    ChampionLocked,
+    GameEnded,
+}
+
+impl WebhookEvent {
+    pub const ALL: [WebhookEvent; 3] = [
+        WebhookEvent::MatchAccepted,
+        WebhookEvent::ChampionLocked,
+        WebhookEvent::GameEnded,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            WebhookEvent::MatchAccepted => "Match accepted",
+            WebhookEvent::ChampionLocked => "Champion locked",
+            WebhookEvent::GameEnded => "Game ended",
+        }
+    }
+
+    /// The placeholders the template of the event can use, besides `{event}`.
+    pub fn placeholders(&self) -> &'static [&'static str] {
+        match self {
+            WebhookEvent::MatchAccepted => &["queue_id"],
+            WebhookEvent::ChampionLocked => &["champion", "champion_id", "position"],
+            WebhookEvent::GameEnded => {
+                &["result", "champion", "champion_id", "game_id", "queue_id"]
+            }
+        }
+    }
+
+    /// A Discord compatible payload, used until a template is set for the event.
+    pub fn default_template(&self) -> &'static str {
+        match self {
+            WebhookEvent::MatchAccepted => r#"{"content": "Match accepted (queue {queue_id})."}"#,
+            WebhookEvent::ChampionLocked => r#"{"content": "Locked in {champion} ({position})."}"#,
+            WebhookEvent::GameEnded => r#"{"content": "{result} as {champion}."}"#,
+        }
+    }
+
+    /// Made up values for the placeholders, used to send test payloads from the settings.
+    fn sample(&self) -> Vec<(&'static str, String)> {
+        let values = [
+            ("queue_id", "420"),
+            ("champion", "Kai'Sa"),
+            ("champion_id", "145"),
+            ("position", "bottom"),
+            ("result", "Victory"),
+            ("game_id", "1234567890"),
+        ];
+        values
+            .into_iter()
+            .filter(|(name, _)| self.placeholders().contains(name))
+            .map(|(name, value)| (name, value.to_owned()))
+            .collect()
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq, Default)]
+#[serde(default)]
+/// The `WebhookSettings` struct holds where webhooks are posted and what they contain.
+///
+/// ### Properties:
+/// * `url`: The URL payloads are posted to, e.g. a Discord webhook URL. Empty to turn webhooks off.
+/// * `enabled`: The events a payload is posted for, none by default.
+/// * `templates`: JSON payloads with `{placeholder}`s, events without one use their default template.
+pub struct WebhookSettings {
+    pub url: String,
+    pub enabled: HashSet<WebhookEvent>,
+    pub templates: HashMap<WebhookEvent, String>,
+}
+
+impl WebhookSettings {
+    /// Reads the webhooks file, the default settings if it doesn't exist or can't be parsed.
+    pub fn load(path: &str) -> Self {
+        std::fs::read_to_string(path)
+            .ok()
+            .and_then(|data| serde_json::from_str(&data).ok())
+            .unwrap_or_default()
+    }
+
+    pub fn save(&self, path: &str) -> std::io::Result<()> {
+        std::fs::write(path, serde_json::to_string_pretty(self)?)
+    }
+
+    pub fn template(&self, event: WebhookEvent) -> &str {
+        match self.templates.get(&event) {
+            Some(template) if !template.trim().is_empty() => template,
+            _ => event.default_template(),
+        }
+    }
+}
+
+/// Fills in the placeholders of a template and checks the result is valid JSON.
+/// Values are escaped as JSON string contents, so placeholders are meant to be used inside strings.
+pub fn render(
+    template: &str,
+    event: WebhookEvent,
+    values: &[(&str, String)],
+) -> Result<String, String> {
+    let escape = |value: &str| {
+        let quoted = serde_json::Value::String(value.to_owned()).to_string();
+        quoted[1..quoted.len() - 1].to_owned()
+    };
+
+    let mut payload = template.replace("{event}", &escape(event.label()));
+    for (name, value) in values {
+        payload = payload.replace(&format!("{{{}}}", name), &escape(value));
+    }
+    serde_json::from_str::<serde_json::Value>(&payload)
+        .map_err(|error| format!("Invalid {} template: {}", event.label(), error))?;
+    Ok(payload)
+}
+
+#[derive(Debug, PartialEq)]
+/// Why a payload couldn't be delivered.
+pub enum DeliveryError {
+    /// The server or the connection failed, the payload is sent again later. Holds how long the server asked to wait.
+    Retry(String, Option<Duration>),
+    /// The server refused the payload, sending it again wouldn't help.
+    Rejected(String),
+}
+
+/// Posts a payload once. Rate limits (`429`) and server errors can be retried, other error statuses can't.
+pub async fn deliver(
+    client: &reqwest::Client,
+    url: &str,
+    payload: &str,
+) -> Result<(), DeliveryError> {
+    let response = client
+        .post(url)
+        .header(reqwest::header::CONTENT_TYPE, "application/json")
+        .body(payload.to_owned())
+        .send()
+        .await
+        .map_err(|error| DeliveryError::Retry(error.to_string(), None))?;
+
+    let status = response.status();
+    if status.is_success() {
+        Ok(())
+    } else if status == reqwest::StatusCode::TOO_MANY_REQUESTS || status.is_server_error() {
+        let retry_after = response
+            .headers()
+            .get(reqwest::header::RETRY_AFTER)
+            .and_then(|value| value.to_str().ok())
+            .and_then(parse_retry_after);
+        Err(DeliveryError::Retry(
+            format!("HTTP {}", status),
+            retry_after,
+        ))
+    } else {
+        Err(DeliveryError::Rejected(format!("HTTP {}", status)))
+    }
+}
+
+/// Reads a `Retry-After` header given in seconds. Negative, infinite and NaN values are ignored,
+/// and the wait is capped so a server can't stall the queue.
+fn parse_retry_after(value: &str) -> Option<Duration> {
+    let seconds = value.trim().parse::<f64>().ok()?;
+    Duration::try_from_secs_f64(seconds)
+        .ok()
+        .map(|delay| delay.min(MAX_RETRY_AFTER))
+}
+
+/// A payload waiting to be posted.
+struct Pending {
+    event: WebhookEvent,
+    url: String,
+    payload: String,
+    attempts: u32,
+    next_try: Instant,
+}
+
+#[derive(Clone, Default)]
+/// The `Webhooks` struct queues payloads for the events webhooks are turned on for, a task posts them
+/// and retries failed ones with an increasing delay.
+///
+/// ### Properties:
+/// * `settings`: The webhook settings, shared with the GUI.
+/// * `status`: The result of the last delivery, shown in the settings.
+/// * `queue`: Payloads waiting to be posted or retried.
+pub struct Webhooks {
+    pub settings: Arc<Mutex<WebhookSettings>>,
+    pub status: Arc<Mutex<Option<String>>>,
+    queue: Arc<Mutex<VecDeque<Pending>>>,
+}
+
+impl Webhooks {
+    pub fn new(settings: WebhookSettings) -> Self {
+        Self {
+            settings: Arc::new(Mutex::new(settings)),
+            ..Default::default()
+        }
+    }
+
+    /// Queues the payload of an event if webhooks are turned on for it.
+    ///
+    /// ### Arguments:
+    /// * `event`: The event that happened.
+    /// * `values`: The values of the placeholders of the event, as `(name, value)` pairs.
+    pub fn send(&self, event: WebhookEvent, values: &[(&str, String)]) {
+        let settings = self.settings.lock().unwrap().clone();
+        if settings.enabled.contains(&event) {
+            self.queue_payload(&settings, event, values);
+        }
+    }
+
+    /// Queues a payload with made up values, to try settings before saving them.
+    pub fn send_test(&self, settings: &WebhookSettings, event: WebhookEvent) {
+        self.queue_payload(settings, event, &event.sample());
+    }
+
+    fn queue_payload(
+        &self,
+        settings: &WebhookSettings,
+        event: WebhookEvent,
+        values: &[(&str, String)],
+    ) {
+        if settings.url.trim().is_empty() {
+            *self.status.lock().unwrap() = Some("No webhook URL set".to_owned());
+            return;
+        }
+        match render(settings.template(event), event, values) {
+            Ok(payload) => self.queue.lock().unwrap().push_back(Pending {
+                event,
+                url: settings.url.trim().to_owned(),
+                payload,
+                attempts: 0,
+                next_try: Instant::now(),
+            }),
+            Err(error) => *self.status.lock().unwrap() = Some(error),
+        }
+    }
+
+    /// Posts the queued payloads from a task of its own. Payloads are sent in order, a failed one is tried
+    /// again after 2, 4, 8 and 16 seconds (or as long as the server asks) before it's given up.
+    pub fn run(&self) {
+        let webhooks = self.clone();
+        tokio::spawn(async move {
+            let client = reqwest::Client::builder()
+                .timeout(Duration::from_secs(10))
+                .build()
+                .unwrap_or_default();
+            loop {
+                let due = {
+                    let mut queue = webhooks.queue.lock().unwrap();
+                    match queue.front() {
+                        Some(pending) if pending.next_try <= Instant::now() => queue.pop_front(),
+                        _ => None,
+                    }
+                };
+                let Some(mut pending) = due else {
+                    tokio::time::sleep(Duration::from_millis(250)).await;
+                    continue;
+                };
+
+                pending.attempts += 1;
+                let status = match deliver(&client, &pending.url, &pending.payload).await {
+                    Ok(()) => format!("{} webhook delivered", pending.event.label()),
+                    Err(DeliveryError::Rejected(reason)) => {
+                        format!("{} webhook rejected: {}", pending.event.label(), reason)
+                    }
+                    Err(DeliveryError::Retry(reason, _)) if pending.attempts >= MAX_ATTEMPTS => {
+                        format!(
+                            "{} webhook failed {} times, gave up: {}",
+                            pending.event.label(),
+                            pending.attempts,
+                            reason
+                        )
+                    }
+                    Err(DeliveryError::Retry(reason, retry_after)) => {
+                        let delay = retry_after
+                            .unwrap_or_else(|| Duration::from_secs(1 << pending.attempts));
+                        let status = format!(
+                            "{} webhook failed ({}), retrying in {}s",
+                            pending.event.label(),
+                            reason,
+                            delay.as_secs()
+                        );
+                        pending.next_try = Instant::now() + delay;
+                        // Stays at the front so payloads keep their order
+                        webhooks.queue.lock().unwrap().push_front(pending);
+                        status
+                    }
+                };
+                *webhooks.status.lock().unwrap() = Some(status);
+            }
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::{Read, Write};
+    use std::net::TcpListener;
+
+    /// Answers each connection with the next response and returns the bodies it received.
+    fn serve(responses: Vec<&'static str>) -> (String, std::thread::JoinHandle<Vec<String>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}/webhook", listener.local_addr().unwrap());
+        let handle = std::thread::spawn(move || {
+            let mut bodies = Vec::new();
+            for response in responses {
+                let (mut stream, _) = listener.accept().unwrap();
+                let mut request = Vec::new();
+                let mut buffer = [0; 1024];
+                let body = loop {
+                    let read = stream.read(&mut buffer).unwrap();
+                    request.extend_from_slice(&buffer[..read]);
+                    let text = String::from_utf8_lossy(&request).to_string();
+                    let Some((head, body)) = text.split_once("\r\n\r\n") else {
+                        continue;
+                    };
+                    let length = head
+                        .lines()
+                        .find_map(|line| {
+                            let (name, value) = line.split_once(':')?;
+                            name.eq_ignore_ascii_case("content-length")
+                                .then(|| value.trim().parse::<usize>().ok())?
+                        })
+                        .unwrap_or(0);
+                    if body.len() >= length {
+                        break body.to_owned();
+                    }
+                };
+                bodies.push(body);
+                stream.write_all(response.as_bytes()).unwrap();
+            }
+            bodies
+        });
+        (url, handle)
+    }
+
+    #[test]
+    fn render_fills_and_escapes_placeholders() {
+        let payload = render(
+            r#"{"content": "{event}: {champion} ({champion_id})"}"#,
+            WebhookEvent::ChampionLocked,
+            &[
+                ("champion", "Kai'Sa \"the\" Daughter".to_owned()),
+                ("champion_id", "145".to_owned()),
+            ],
+        )
+        .unwrap();
+        let value: serde_json::Value = serde_json::from_str(&payload).unwrap();
+
+        assert_eq!(
+            value["content"],
+            "Champion locked: Kai'Sa \"the\" Daughter (145)"
+        );
+    }
+
+    #[test]
+    fn render_rejects_invalid_json() {
+        assert!(render("{\"content\": ", WebhookEvent::GameEnded, &[]).is_err());
+    }
+
+    #[test]
+    fn default_templates_render_with_sample_values() {
+        for event in WebhookEvent::ALL {
+            let payload = render(event.default_template(), event, &event.sample()).unwrap();
+            for name in event.placeholders() {
+                assert!(!payload.contains(&format!("{{{}}}", name)));
+            }
+        }
+    }
+
+    #[test]
+    fn retry_after_ignores_invalid_values_and_is_capped() {
+        assert_eq!(parse_retry_after(" 3 "), Some(Duration::from_secs(3)));
+        assert_eq!(parse_retry_after("0.5"), Some(Duration::from_millis(500)));
+        assert_eq!(parse_retry_after("86400"), Some(MAX_RETRY_AFTER));
+        for value in ["-1", "inf", "-inf", "NaN", "1e400", "soon", ""] {
+            assert_eq!(parse_retry_after(value), None, "{}", value);
+        }
+    }
+
+    #[tokio::test]
+    async fn deliver_posts_payload_and_classifies_failures() {
+        let (url, server) = serve(vec![
+            "HTTP/1.1 204 No Content\r\nConnection: close\r\n\r\n",
+            "HTTP/1.1 429 Too Many Requests\r\nRetry-After: 3\r\nContent-Length: 0\r\nConnection: close\r\n\r\n",
+            "HTTP/1.1 500 Internal Server Error\r\nRetry-After: -1\r\nContent-Length: 0\r\nConnection: close\r\n\r\n",
+            "HTTP/1.1 400 Bad Request\r\nContent-Length: 0\r\nConnection: close\r\n\r\n",
+        ]);
+        let client = reqwest::Client::new();
+        let payload = r#"{"content": "Victory as Jhin."}"#;
+
+        assert_eq!(deliver(&client, &url, payload).await, Ok(()));
+        assert_eq!(
+            deliver(&client, &url, payload).await,
+            Err(DeliveryError::Retry(
+                "HTTP 429 Too Many Requests".to_owned(),
+                Some(Duration::from_secs(3))
+            ))
+        );
+        assert!(matches!(
+            deliver(&client, &url, payload).await,
+            Err(DeliveryError::Retry(_, None))
+        ));
+        assert!(matches!(
+            deliver(&client, &url, payload).await,
+            Err(DeliveryError::Rejected(_))
+        ));
+        assert_eq!(server.join().unwrap(), vec![payload; 4]);
+    }
+
+    #[tokio::test]
+    async fn queued_payload_is_retried_until_delivered() {
+        let (url, server) = serve(vec![
+            "HTTP/1.1 503 Service Unavailable\r\nRetry-After: 0\r\nContent-Length: 0\r\nConnection: close\r\n\r\n",
+            "HTTP/1.1 204 No Content\r\nConnection: close\r\n\r\n",
+        ]);
+        let webhooks = Webhooks::new(WebhookSettings {
+            url,
+            enabled: HashSet::from([WebhookEvent::GameEnded]),
+            templates: HashMap::new(),
+        });
+        webhooks.run();
+        webhooks.send(
+            WebhookEvent::GameEnded,
+            &[
+                ("result", "Defeat".to_owned()),
+                ("champion", "Yone".to_owned()),
+            ],
+        );
+        webhooks.send(
+            WebhookEvent::MatchAccepted,
+            &[("queue_id", "420".to_owned())],
+        );
+
+        let bodies = tokio::task::spawn_blocking(move || server.join().unwrap())
+            .await
+            .unwrap();
+        assert_eq!(bodies, vec![r#"{"content": "Defeat as Yone."}"#; 2]);
+        let delivered = Some("Game ended webhook delivered".to_owned());
+        for _ in 0..20 {
+            if *webhooks.status.lock().unwrap() == delivered {
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(50)).await;
+        }
+        assert_eq!(*webhooks.status.lock().unwrap(), delivered);
+        assert!(webhooks.queue.lock().unwrap().is_empty());
+    }
+}